@@ -10,6 +10,10 @@ where
     projection: Projection,
     controls: Controls,
     changed: bool,
+    /// Rounds the view translation to whole pixels.
+    pixel_snap: bool,
+    /// Pixels per unit of view translation used when snapping.
+    pixels_per_unit: f32,
 }
 
 impl<Controls> Camera<Controls>
@@ -33,6 +37,8 @@ where
             projection,
             controls,
             changed: true,
+            pixel_snap: false,
+            pixels_per_unit: 1.0,
         }
     }
 
@@ -60,10 +66,51 @@ where
         changed
     }
 
+    /// Returns the view matrix. When pixel snapping is enabled the
+    /// translation is rounded to whole pixels.
     pub fn view(&self) -> Mat4 {
+        let mut view = self.controls.view();
+
+        if self.pixel_snap {
+            let ppu = self.pixels_per_unit;
+
+            view.w_axis.x = (view.w_axis.x * ppu).round() / ppu;
+            view.w_axis.y = (view.w_axis.y * ppu).round() / ppu;
+        }
+
+        view
+    }
+
+    /// Returns the view matrix without pixel snapping. Use this for smooth
+    /// follow interpolation.
+    pub fn raw_view(&self) -> Mat4 {
         self.controls.view()
     }
 
+    /// Enables or disables rounding the view translation to whole pixels.
+    /// This stops pixel art from shimmering when the camera moves.
+    pub fn set_pixel_snap(&mut self, pixel_snap: bool) {
+        self.pixel_snap = pixel_snap;
+        self.changed = true;
+    }
+
+    pub fn pixel_snap(&self) -> bool {
+        self.pixel_snap
+    }
+
+    /// Sets how many pixels one unit of view translation covers.
+    /// Default is 1.0. Values of 0 or less are ignored.
+    pub fn set_pixels_per_unit(&mut self, pixels_per_unit: f32) {
+        if pixels_per_unit > 0.0 {
+            self.pixels_per_unit = pixels_per_unit;
+            self.changed = true;
+        }
+    }
+
+    pub fn pixels_per_unit(&self) -> f32 {
+        self.pixels_per_unit
+    }
+
     pub fn scale(&self) -> f32 {
         self.controls.scale()
     }
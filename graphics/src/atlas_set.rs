@@ -1,21 +1,100 @@
 use crate::{
-    AHashMap, AHashSet, GpuRenderer, GraphicsError, TextureGroup,
-    TextureLayout, UVec3,
+    AHashMap, AHashSet, AtlasUploadError, GpuRenderer, GraphicsError,
+    TextureGroup, TextureLayout, UVec3,
 };
+use log::debug;
 use lru::LruCache;
 use slab::Slab;
-use std::{hash::Hash, rc::Rc};
+use std::{
+    hash::Hash,
+    num::NonZeroUsize,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
 use wgpu::{BindGroup, BindGroupLayout, TextureUsages};
 
 mod allocation;
 mod allocator;
 mod atlas;
+mod cache;
 mod migration;
 
-pub use allocation::Allocation;
+pub use allocation::{Allocation, AtlasUv};
 pub use allocator::Allocator;
 pub use atlas::Atlas;
+pub use cache::{AtlasCache, AtlasCacheEntry, AtlasCacheLayer};
 use migration::*;
+
+/// Typed index of a Texture stored within an [`AtlasSet`].
+/// Returned when uploading and used to look the Texture back up so it can
+/// not be mixed up with a Buffer [`crate::Index`] or other ids.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TextureId(pub usize);
+
+impl From<TextureId> for usize {
+    fn from(id: TextureId) -> Self {
+        id.0
+    }
+}
+
+/// How many Texture layers an [`AtlasSet`] allocates when it needs to grow.
+/// Every growth beyond the allocated layers recreates the Texture and copies
+/// the existing layers, so headroom trades Vram for fewer copies.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AtlasGrowth {
+    /// Only allocate the layers needed.
+    #[default]
+    Exact,
+    /// Double the allocated layers like a Vec.
+    Double,
+    /// Allocate this many extra layers past the ones needed.
+    Headroom(u32),
+}
+
+/// How the color channels of an [`AtlasSet`]'s Textures relate to alpha.
+/// Premultiplied Textures get divided by alpha in the [`crate::Image`] and
+/// [`crate::Rect`] shaders before tinting so their edges blend without a
+/// dark halo.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AlphaMode {
+    /// Color is not multiplied by alpha. This is what most PNGs store.
+    #[default]
+    Straight = 0,
+    /// Color was already multiplied by alpha.
+    Premultiplied = 1,
+}
+
+/// Options for [`AtlasSet::frame_maintenance`].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaintenancePolicy {
+    /// Max amount of [`AtlasSet::defragment`] calls per frame. 0 disables
+    /// defragmenting. Default is 1.
+    pub defrag_budget: usize,
+    /// Removes least recently used Allocations over the cache capacity set
+    /// with [`AtlasSet::set_cache_capacity`]. Default is true.
+    pub evict: bool,
+    /// Clears last_used with [`AtlasSet::trim`] after the other steps.
+    /// Default is true.
+    pub trim: bool,
+}
+
+impl Default for MaintenancePolicy {
+    fn default() -> Self {
+        Self {
+            defrag_budget: 1,
+            evict: true,
+            trim: true,
+        }
+    }
+}
+
+/// Counter used to give each [`AtlasSet`] a unique id.
+static NEXT_ATLAS_ID: AtomicU64 = AtomicU64::new(0);
 /**
  * AtlasSet is used to hold and contain the data of many Atlas layers.
  * Each Atlas keeps track of the allocations allowed. Each allocation is a
@@ -82,6 +161,16 @@ pub struct AtlasSet<U: Hash + Eq + Clone = String, Data: Copy + Default = i32> {
     pub texture_group: TextureGroup,
     /// Used to Migrate Textures to reduce Fragmentation.
     pub migration: Option<MigrationTask>,
+    /// How many layers to allocate when growing. Default is Exact.
+    pub growth: AtlasGrowth,
+    /// How the uploaded Textures store alpha. Default is Straight.
+    pub alpha_mode: AlphaMode,
+    /// Soft cap on the amount of cached Allocations. When exceeded the least
+    /// recently used Allocations not in use are removed. None is unbounded.
+    cache_capacity: Option<NonZeroUsize>,
+    /// Unique id of this AtlasSet. Used by renderers to detect buffer layers
+    /// that mix objects from different AtlasSets.
+    id: u64,
 }
 
 impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
@@ -90,10 +179,14 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         width: u32,
         height: u32,
         data: Data,
-    ) -> Option<Allocation<Data>> {
+    ) -> Result<Allocation<Data>, AtlasUploadError> {
+        if width == 0 || height == 0 {
+            return Err(AtlasUploadError::InvalidSize);
+        }
+
         /* Check if the allocation would fit. */
         if width > self.size || height > self.size {
-            return None;
+            return Err(AtlasUploadError::TooLarge { max: self.size });
         }
 
         /* Try allocating from an existing layer. */
@@ -103,7 +196,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             }
 
             if let Some(allocation) = layer.allocator.allocate(width, height) {
-                return Some(Allocation {
+                return Ok(Allocation {
                     allocation,
                     layer: i,
                     data,
@@ -115,7 +208,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         It doesnt matter here if we are migrating  or not here this saves us some time.*/
         if !self.use_ref_count {
             loop {
-                let (&id, _) = self.cache.peek_lru()?;
+                let (&id, _) =
+                    self.cache.peek_lru().ok_or(AtlasUploadError::Full)?;
 
                 //Check if ID has been used yet?
                 if self.last_used.contains(&id) {
@@ -123,13 +217,16 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                     break;
                 }
 
-                if let Some(layer_id) = self.remove(id) {
-                    let layer = self.layers.get_mut(layer_id)?;
+                if let Some(layer_id) = self.remove(TextureId(id)) {
+                    let layer = self
+                        .layers
+                        .get_mut(layer_id)
+                        .ok_or(AtlasUploadError::Full)?;
 
                     if let Some(allocation) =
                         layer.allocator.allocate(width, height)
                     {
-                        return Some(Allocation {
+                        return Ok(Allocation {
                             allocation,
                             layer: layer_id,
                             data,
@@ -142,8 +239,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         /* Add a new layer, as we found no layer to allocate from and could
         not retrieve any old allocations to use. */
 
-        if self.layers.len() + 1 == self.max_layers {
-            return None;
+        if self.layers.len() >= self.max_layers {
+            return Err(AtlasUploadError::Full);
         }
 
         let mut layer = Atlas::new(self.size);
@@ -155,7 +252,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 migrating.avaliable.push(self.layers.len() - 1);
             }
 
-            return Some(Allocation {
+            return Ok(Allocation {
                 allocation,
                 layer: self.layers.len() - 1,
                 data,
@@ -163,7 +260,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         }
 
         /* We are out of luck. */
-        None
+        Err(AtlasUploadError::Full)
     }
 
     //TODO Add shrink that takes layers using a unload boolean and also promote each layers.
@@ -173,13 +270,45 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             return;
         }
 
+        // Preallocated textures already have room so we avoid rebuilding the bind group.
+        if self.layers.len() as u32 <= self.texture.depth_or_array_layers() {
+            return;
+        }
+
+        let needed = self.layers.len() as u32;
+        let layer_count = match self.growth {
+            AtlasGrowth::Exact => needed,
+            AtlasGrowth::Double => {
+                needed.max(self.texture.depth_or_array_layers() * 2)
+            }
+            AtlasGrowth::Headroom(extra) => needed.saturating_add(extra),
+        }
+        .min(self.max_layers as u32);
+        let amount_to_copy = self.layers.len() - amount;
+
+        self.rebuild_texture(layer_count, amount_to_copy as u32, renderer);
+    }
+
+    // Creates a new texture with layer_count layers, copies the first copy_layers
+    // into it and rebuilds the bind group.
+    fn rebuild_texture(
+        &mut self,
+        layer_count: u32,
+        copy_layers: u32,
+        renderer: &GpuRenderer,
+    ) {
+        debug!(
+            "AtlasSet rebuilding texture and bind group with {} layers",
+            layer_count
+        );
+
         let texture =
             renderer.device().create_texture(&wgpu::TextureDescriptor {
                 label: Some("Texture"),
                 size: wgpu::Extent3d {
                     width: self.size,
                     height: self.size,
-                    depth_or_array_layers: self.layers.len() as u32,
+                    depth_or_array_layers: layer_count,
                 },
                 mip_level_count: 1,
                 sample_count: 1,
@@ -191,20 +320,14 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 view_formats: &[self.format],
             });
 
-        let amount_to_copy = self.layers.len() - amount;
-
         let mut encoder = renderer.device().create_command_encoder(
             &wgpu::CommandEncoderDescriptor {
                 label: Some("Texture command encoder"),
             },
         );
 
-        for (i, _) in self.layers.iter_mut().take(amount_to_copy).enumerate() {
-            let origin = wgpu::Origin3d {
-                x: 0,
-                y: 0,
-                z: i as u32,
-            };
+        for i in 0..copy_layers {
+            let origin = wgpu::Origin3d { x: 0, y: 0, z: i };
 
             encoder.copy_texture_to_texture(
                 wgpu::TexelCopyTextureInfo {
@@ -237,7 +360,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
                 base_mip_level: 0,
                 mip_level_count: Some(1),
                 base_array_layer: 0,
-                array_layer_count: Some(self.layers.len() as u32),
+                array_layer_count: Some(layer_count),
                 usage: Some(TextureUsages::COPY_DST | TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING),
             });
         let atlas_layout: Rc<BindGroupLayout> = renderer
@@ -260,18 +383,119 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         format: wgpu::TextureFormat,
         use_ref_count: bool,
         size: u32,
+    ) -> Self {
+        let layers = Self::min_layers(renderer);
+
+        Self::new_with_layers(renderer, format, use_ref_count, size, layers)
+    }
+
+    /// Returns the least amount of layers an [`AtlasSet`] Texture can start with.
+    /// OpenGL treats a Texture with a single layer as a plain 2D Texture which
+    /// can not be bound as a 2D array, so the Gl backend needs at least 2.
+    /// Every other backend only needs 1.
+    ///
+    pub fn min_layers(renderer: &GpuRenderer) -> u32 {
+        if renderer.backend == wgpu::Backend::Gl {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Creates a new [`AtlasSet`] whose allocations do not depend on the
+    /// backend or device limits, so the same uploads in the same order give
+    /// the same layers and positions on every machine.
+    ///
+    /// Pins these knobs:
+    /// - size: Used as is instead of being limited to the device.
+    /// - layers: Starts with at least 2 layers on every backend instead of
+    ///   [`AtlasSet::min_layers`].
+    /// - max_layers: Taken from the arguments instead of
+    ///   limits.max_texture_array_layers. layer_check_limit is derived from it.
+    ///
+    /// deallocations_limit, layer_free_limit and growth already use fixed
+    /// defaults. Removals, trims and defragmenting still change the layout
+    /// so they must also be called in the same order.
+    /// Returns [`GraphicsError::AtlasLimitExceeded`] if the device can not
+    /// hold the size or max_layers and [`GraphicsError::AtlasSizeTooSmall`]
+    /// if the size is below 256.
+    ///
+    /// # Arguments
+    /// - format: [`wgpu::TextureFormat`] the texture layers will need to be.
+    /// - use_ref_count: Mostly used for Glyph Storage and Auto Removal.
+    /// - size: Used for both Width and Height. Must be at least 256.
+    /// - layers: Layers to start with. Limited between 2 and max_layers.
+    /// - max_layers: Most layers the [`AtlasSet`] may hold. At least 2.
+    ///
+    pub fn new_deterministic(
+        renderer: &mut GpuRenderer,
+        format: wgpu::TextureFormat,
+        use_ref_count: bool,
+        size: u32,
+        layers: u32,
+        max_layers: u32,
+    ) -> Result<Self, GraphicsError> {
+        let limits = renderer.device().limits();
+
+        if size < 256 {
+            return Err(GraphicsError::AtlasSizeTooSmall { size, min: 256 });
+        }
+
+        if size > limits.max_texture_dimension_2d {
+            return Err(GraphicsError::AtlasLimitExceeded {
+                needed: size,
+                max: limits.max_texture_dimension_2d,
+            });
+        }
+
+        if max_layers > limits.max_texture_array_layers {
+            return Err(GraphicsError::AtlasLimitExceeded {
+                needed: max_layers,
+                max: limits.max_texture_array_layers,
+            });
+        }
+
+        let max_layers = max_layers.max(2);
+        let layers = layers.clamp(2, max_layers);
+        let mut atlas = Self::new_with_layers(
+            renderer,
+            format,
+            use_ref_count,
+            size,
+            layers,
+        );
+
+        atlas.max_layers = max_layers as usize;
+        atlas.layer_check_limit = (max_layers as f64 * 0.8) as usize;
+        Ok(atlas)
+    }
+
+    /// Creates a new [`AtlasSet`] starting with a set amount of Texture layers.
+    ///
+    /// # Arguments
+    /// - format: [`wgpu::TextureFormat`] the texture layers will need to be.
+    /// - use_ref_count: Mostly used for Glyph Storage and Auto Removal.
+    /// - size: Used for both Width and Height. Limited to max of limits.max_texture_dimension_2d and min of 256.
+    /// - layers: Layers to start with. Limited between [`AtlasSet::min_layers`] and limits.max_texture_array_layers.
+    ///
+    pub fn new_with_layers(
+        renderer: &mut GpuRenderer,
+        format: wgpu::TextureFormat,
+        use_ref_count: bool,
+        size: u32,
+        layers: u32,
     ) -> Self {
         let limits = renderer.device().limits();
         let size = size.clamp(256, limits.max_texture_dimension_2d);
+        let layers = layers.clamp(
+            Self::min_layers(renderer),
+            limits.max_texture_array_layers.max(1),
+        );
 
         let extent = wgpu::Extent3d {
             width: size,
             height: size,
-            depth_or_array_layers: if renderer.backend == wgpu::Backend::Gl {
-                2
-            } else {
-                1
-            },
+            depth_or_array_layers: layers,
         };
 
         let texture =
@@ -296,7 +520,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             base_mip_level: 0,
             mip_level_count: Some(1),
             base_array_layer: 0,
-            array_layer_count: Some(1),
+            array_layer_count: Some(layers),
             usage: Some(TextureUsages::COPY_DST | TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING),
         });
 
@@ -307,11 +531,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
 
         Self {
             texture,
-            layers: if renderer.backend == wgpu::Backend::Gl {
-                vec![Atlas::new(size), Atlas::new(size)]
-            } else {
-                vec![Atlas::new(size)]
-            },
+            layers: (0..layers).map(|_| Atlas::new(size)).collect(),
             store: Slab::with_capacity(512),
             lookup: AHashMap::new(),
             size,
@@ -326,9 +546,19 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             use_ref_count,
             texture_group,
             migration: None,
+            growth: AtlasGrowth::Exact,
+            alpha_mode: AlphaMode::Straight,
+            cache_capacity: None,
+            id: NEXT_ATLAS_ID.fetch_add(1, Ordering::Relaxed),
         }
     }
 
+    /// Returns the unique id of the [`AtlasSet`].
+    ///
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     /// Uploads a new Texture Byte Array into the GPU AtlasSets Layer.
     ///
     pub fn upload_allocation(
@@ -376,6 +606,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
     ///
     /// This Does not Empty the [`AtlasSet`]s GPU Texture Buffer.
     /// As we normally just overwrite the buffer when we add new Allocations.
+    /// Use [`AtlasSet::clear_and_zero`] to also zero the GPU Texture.
     ///
     pub fn clear(&mut self) {
         for layer in self.layers.iter_mut() {
@@ -388,6 +619,68 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         self.last_used.clear();
     }
 
+    /// Clears all information of stored Textures and Allocations
+    /// and also zeroes every layer of the [`AtlasSet`]s GPU Texture.
+    ///
+    /// This prevents old texels from bleeding into new Allocations when
+    /// reusing the [`AtlasSet`]. Uses [`wgpu::CommandEncoder::clear_texture`]
+    /// when [`wgpu::Features::CLEAR_TEXTURE`] is enabled otherwise it uploads
+    /// a zeroed buffer per layer.
+    ///
+    pub fn clear_and_zero(&mut self, renderer: &GpuRenderer) {
+        self.clear();
+
+        let layers = self.texture.depth_or_array_layers();
+
+        if renderer
+            .device()
+            .features()
+            .contains(wgpu::Features::CLEAR_TEXTURE)
+        {
+            let mut encoder = renderer.device().create_command_encoder(
+                &wgpu::CommandEncoderDescriptor {
+                    label: Some("atlas clear encoder"),
+                },
+            );
+
+            encoder.clear_texture(
+                &self.texture,
+                &wgpu::ImageSubresourceRange::default(),
+            );
+            renderer.queue().submit(std::iter::once(encoder.finish()));
+            return;
+        }
+
+        let bytes_per_row = self.size * self.bytes_per_pixel();
+        let zeroed = vec![0u8; (bytes_per_row * self.size) as usize];
+
+        for layer in 0..layers {
+            renderer.queue().write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &self.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: layer,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &zeroed,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(self.size),
+                },
+                wgpu::Extent3d {
+                    width: self.size,
+                    height: self.size,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+    }
+
     /// Defragments Textures when they reach a specific threshhold.
     ///
     /// # Strategy
@@ -456,13 +749,13 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             true
         } else {
             let mut task = MigrationTask::default();
-            let mut total = 0;
-
-            let _ = self.layers.iter().inspect(|layer| {
-                if layer.allocator.deallocations() >= self.deallocations_limit {
-                    total += 1;
-                }
-            });
+            let total = self
+                .layers
+                .iter()
+                .filter(|layer| {
+                    layer.allocator.deallocations() >= self.deallocations_limit
+                })
+                .count();
 
             if total == 0 {
                 return Ok(false);
@@ -478,6 +771,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             }
 
             if !task.migrating.is_empty() {
+                task.total = task.migrating.len();
                 self.migration = Some(task);
             }
 
@@ -485,6 +779,99 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         })
     }
 
+    /// Runs the per frame upkeep in the order it needs to happen.
+    /// Defragments up to the policy's budget, then evicts Allocations over
+    /// the cache capacity while last_used still marks what this frame used
+    /// and then trims last_used. Call this once a frame after rendering.
+    ///
+    /// # Returns
+    /// bool indicating if any Allocations moved so Objects need to be rebuilt
+    /// like with [`AtlasSet::defragment`].
+    ///
+    pub fn frame_maintenance(
+        &mut self,
+        renderer: &GpuRenderer,
+        policy: MaintenancePolicy,
+    ) -> Result<bool, GraphicsError> {
+        let mut migrated = false;
+
+        for _ in 0..policy.defrag_budget {
+            let changed = self.defragment(renderer)?;
+            migrated |= changed;
+
+            // Stop once nothing needs defragmenting or the last task finished.
+            if self.migration.is_none() {
+                break;
+            }
+        }
+
+        if policy.evict {
+            self.evict_over_capacity(None);
+        }
+
+        if policy.trim {
+            self.trim();
+        }
+
+        Ok(migrated)
+    }
+
+    /// Returns (layers remaining, total layers) of the current
+    /// [`MigrationTask`] or None when no defragmentation is running.
+    ///
+    pub fn migration_progress(&self) -> Option<(usize, usize)> {
+        self.migration
+            .as_ref()
+            .map(|task| (task.migrating.len(), task.total))
+    }
+
+    /// Sets a soft cap on the amount of cached Allocations.
+    /// When exceeded the least recently used Allocations that are not in
+    /// last_used, and with use_ref_count have no extra references, are
+    /// removed from the atlas. None removes the cap.
+    ///
+    pub fn set_cache_capacity(&mut self, capacity: Option<NonZeroUsize>) {
+        self.cache_capacity = capacity;
+        self.evict_over_capacity(None);
+    }
+
+    /// Returns the soft cap on the amount of cached Allocations.
+    ///
+    pub fn cache_capacity(&self) -> Option<NonZeroUsize> {
+        self.cache_capacity
+    }
+
+    /// Removes unused Allocations while over the cache capacity.
+    /// keep is never removed so a fresh upload stays valid.
+    fn evict_over_capacity(&mut self, keep: Option<usize>) {
+        let Some(capacity) = self.cache_capacity else {
+            return;
+        };
+
+        let over = self.cache.len().saturating_sub(capacity.get());
+
+        if over == 0 {
+            return;
+        }
+
+        let evict: Vec<usize> = self
+            .cache
+            .iter()
+            .rev()
+            .filter(|(id, refcount)| {
+                Some(**id) != keep
+                    && !self.last_used.contains(id)
+                    && (!self.use_ref_count || **refcount <= 1)
+            })
+            .map(|(id, _)| *id)
+            .take(over)
+            .collect();
+
+        for id in evict {
+            self.remove(TextureId(id));
+        }
+    }
+
     /// Clears the last_used cache's.
     ///
     pub fn trim(&mut self) {
@@ -502,15 +889,15 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
 
     /// Promotes the cache's Allocation by index making it recently used..
     ///
-    pub fn promote(&mut self, id: usize) {
-        self.cache.promote(&id);
-        self.last_used.insert(id);
+    pub fn promote(&mut self, id: TextureId) {
+        self.cache.promote(&id.0);
+        self.last_used.insert(id.0);
     }
 
     /// Gets the [`Allocation`]'s index if it exists.
     ///
-    pub fn lookup(&self, key: &U) -> Option<usize> {
-        self.lookup.get(key).copied()
+    pub fn lookup(&self, key: &U) -> Option<TextureId> {
+        self.lookup.get(key).copied().map(TextureId)
     }
 
     /// Gets using key the reference of [`Allocation`] with key if it exists.
@@ -525,8 +912,18 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
 
     /// Gets using index the reference of [`Allocation`] with key if it exists.
     ///
-    pub fn peek(&mut self, id: usize) -> Option<&(Allocation<Data>, U)> {
-        self.store.get(id)
+    pub fn peek(&self, id: TextureId) -> Option<&(Allocation<Data>, U)> {
+        self.store.get(id.0)
+    }
+
+    /// Gets using index the normalized UV rect and layer of the [`Allocation`].
+    /// Does not promote the [`Allocation`] within the cache.
+    ///
+    pub fn normalized_rect(&self, id: TextureId) -> Option<AtlasUv> {
+        self.store.get(id.0).map(|(allocation, _)| AtlasUv {
+            rect: allocation.normalized_rect(self.size),
+            layer: allocation.layer,
+        })
     }
 
     /// If [`Allocation`] using key exists.
@@ -537,8 +934,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
 
     /// If [`Allocation`] at id exists.
     ///
-    pub fn contains(&mut self, id: usize) -> bool {
-        self.store.contains(id)
+    pub fn contains(&mut self, id: TextureId) -> bool {
+        self.store.contains(id.0)
     }
 
     /// Gets using key the [`Allocation`] if it exists.
@@ -558,7 +955,9 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
     /// Gets using index the [`Allocation`] if it exists.
     /// Also Increments the Cache and adds to last_used list.
     ///
-    pub fn get(&mut self, id: usize) -> Option<Allocation<Data>> {
+    pub fn get(&mut self, id: TextureId) -> Option<Allocation<Data>> {
+        let id = id.0;
+
         if let Some((allocation, _)) = self.store.get(id) {
             self.cache.promote(&id);
             self.last_used.insert(id);
@@ -598,7 +997,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
     ///
     /// returns the layer id if removed otherwise None for everything else.
     ///
-    pub fn remove(&mut self, id: usize) -> Option<usize> {
+    pub fn remove(&mut self, id: TextureId) -> Option<usize> {
+        let id = id.0;
         let refcount = self.cache.pop(&id)?.saturating_sub(1);
 
         if self.use_ref_count && refcount > 0 {
@@ -632,9 +1032,32 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         height: u32,
         data: Data,
         renderer: &GpuRenderer,
-    ) -> Option<usize> {
+    ) -> Option<TextureId> {
+        self.try_upload(key, bytes, width, height, data, renderer)
+            .ok()
+    }
+
+    /// Uploads Texture byte array to the AtlasSet returning the created [`Allocation`]s Index.
+    /// Returns a [`AtlasUploadError`] telling why the upload failed.
+    ///
+    /// # Arguments
+    /// - bytes: Textures Byte array.
+    /// - width: Width of the Texture.
+    /// - height: Height of the Texture.
+    /// - data: any specail generic data for the texture.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_upload(
+        &mut self,
+        key: U,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        data: Data,
+        renderer: &GpuRenderer,
+    ) -> Result<TextureId, AtlasUploadError> {
         if let Some(&id) = self.lookup.get(&key) {
-            Some(id)
+            Ok(TextureId(id))
         } else {
             let allocation = {
                 let nlayers = self.layers.len();
@@ -649,7 +1072,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             self.layers[allocation.layer].insert_index(id);
             self.lookup.insert(key, id);
             self.cache.push(id, 1);
-            Some(id)
+            self.evict_over_capacity(Some(id));
+            Ok(TextureId(id))
         }
     }
 
@@ -670,10 +1094,34 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         height: u32,
         data: Data,
         renderer: &GpuRenderer,
-    ) -> Option<(usize, Allocation<Data>)> {
+    ) -> Option<(TextureId, Allocation<Data>)> {
+        self.try_upload_with_alloc(key, bytes, width, height, data, renderer)
+            .ok()
+    }
+
+    /// Uploads Texture byte array to the AtlasSet returning the created [`Allocation`] and Index.
+    /// Returns a [`AtlasUploadError`] telling why the upload failed.
+    ///
+    /// # Arguments
+    /// - bytes: Textures Byte array.
+    /// - width: Width of the Texture.
+    /// - height: Height of the Texture.
+    /// - data: any specail generic data for the texture.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_upload_with_alloc(
+        &mut self,
+        key: U,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        data: Data,
+        renderer: &GpuRenderer,
+    ) -> Result<(TextureId, Allocation<Data>), AtlasUploadError> {
         if let Some(&id) = self.lookup.get(&key) {
-            let (allocation, _) = self.store.get(id)?;
-            Some((id, *allocation))
+            let (allocation, _) =
+                self.store.get(id).ok_or(AtlasUploadError::Full)?;
+            Ok((TextureId(id), *allocation))
         } else {
             let allocation = {
                 let nlayers = self.layers.len();
@@ -688,7 +1136,8 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
             self.layers[allocation.layer].insert_index(id);
             self.lookup.insert(key.clone(), id);
             self.cache.push(id, 1);
-            Some((id, allocation))
+            self.evict_over_capacity(Some(id));
+            Ok((TextureId(id), allocation))
         }
     }
 
@@ -703,6 +1152,71 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         &self.texture_group.bind_group
     }
 
+    /// Preallocates the Texture to hold layers amount of layers up front.
+    /// Growing within this amount only adds the layer to the allocator and
+    /// does not recreate the texture or bind group.
+    /// layers is limited between the current layer count and max_layers.
+    ///
+    pub fn with_preallocated_layers(
+        mut self,
+        renderer: &GpuRenderer,
+        layers: usize,
+    ) -> Self {
+        let layers = layers.clamp(self.layers.len(), self.max_layers) as u32;
+        let current = self.texture.depth_or_array_layers();
+
+        if layers > current {
+            self.rebuild_texture(layers, current, renderer);
+        }
+
+        self
+    }
+
+    /// Returns how many layers the Texture can hold before it needs to be recreated.
+    ///
+    pub fn layer_capacity(&self) -> u32 {
+        self.texture.depth_or_array_layers()
+    }
+
+    /// Returns the GPU memory in bytes used by the Texture.
+    /// Counts every allocated layer including preallocated ones and
+    /// handles block compressed formats.
+    ///
+    pub fn gpu_bytes(&self) -> u64 {
+        let (block_width, block_height) = self.format.block_dimensions();
+        let block_size = self.format.block_copy_size(None).unwrap_or(4);
+        let blocks = self.size.div_ceil(block_width) as u64
+            * self.size.div_ceil(block_height) as u64;
+
+        blocks * block_size as u64 * self.layer_capacity() as u64
+    }
+
+    /// Sets how many Texture layers to allocate when the [`AtlasSet`] grows.
+    ///
+    pub fn with_growth(mut self, growth: AtlasGrowth) -> Self {
+        self.growth = growth;
+        self
+    }
+
+    /// Sets how the Textures uploaded into the [`AtlasSet`] store alpha.
+    ///
+    pub fn with_alpha_mode(mut self, alpha_mode: AlphaMode) -> Self {
+        self.alpha_mode = alpha_mode;
+        self
+    }
+
+    /// Returns the alpha_mode value the shaders use for this [`AtlasSet`].
+    /// 0 is Straight, 1 is Premultiplied and 2 is Premultiplied in a sRGB
+    /// format which needs dividing before it is decoded.
+    ///
+    pub(crate) fn shader_alpha_mode(&self) -> u32 {
+        match self.alpha_mode {
+            AlphaMode::Straight => 0,
+            AlphaMode::Premultiplied if self.format.is_srgb() => 2,
+            AlphaMode::Premultiplied => 1,
+        }
+    }
+
     /// Returns a [`BindGroup`] Reference to the AtlasSets Texture Binding.
     pub fn with_deallocations_limit(mut self, limit: usize) -> Self {
         self.deallocations_limit = limit;
@@ -1,3 +1,5 @@
+use crate::Vec4;
+
 /// [`guillotiere::Allocation`] handler for [`crate::AtlasSet`].
 ///
 #[derive(Copy, Clone, Debug)]
@@ -33,4 +35,27 @@ impl<Data: Copy + Default> Allocation<Data> {
 
         (size.width as u32, size.height as u32)
     }
+
+    /// Returns the x, y, width and height normalized to 0.0..1.0
+    /// within the atlas.
+    ///
+    /// # Arguments
+    /// - atlas_size: Width and Height of the atlas layer.
+    ///
+    pub fn normalized_rect(&self, atlas_size: u32) -> Vec4 {
+        let (x, y, w, h) = self.rect();
+
+        Vec4::new(x as f32, y as f32, w as f32, h as f32)
+            / atlas_size.max(1) as f32
+    }
+}
+
+/// Normalized UV rect of an [`Allocation`] and the layer it is within.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AtlasUv {
+    /// x, y, width and height normalized to 0.0..1.0 within the layer.
+    pub rect: Vec4,
+    /// Texture Atlas Array Layer.
+    pub layer: usize,
 }
@@ -1,5 +1,6 @@
 /// [`guillotiere::AtlasAllocator`] handler for [`crate::AtlasSet`].
 ///
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Allocator {
     /// [`guillotiere::AtlasAllocator`] holding Allocations for Textures.
     allocator: guillotiere::AtlasAllocator,
@@ -24,6 +24,16 @@ impl Atlas {
         }
     }
 
+    /// Creates a Atlas from an existing Allocator's layout.
+    ///
+    pub fn from_allocator(allocator: Allocator) -> Self {
+        Self {
+            allocator,
+            allocated: AIndexSet::default(),
+            migrating: false,
+        }
+    }
+
     /// Allocates a Spot within the Texture for uploading too.
     ///
     pub fn allocate(
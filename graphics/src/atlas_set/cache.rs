@@ -0,0 +1,353 @@
+use crate::{
+    AHashSet, Allocation, Allocator, Atlas, AtlasSet, GpuRenderer,
+    GraphicsError, OtherError,
+};
+use serde::{Deserialize, Serialize};
+use slab::Slab;
+use std::hash::Hash;
+
+/// A single Texture Allocation stored within an [`AtlasCache`].
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AtlasCacheEntry<U, Data> {
+    /// [`crate::TextureId`] the Texture had when exported.
+    pub id: usize,
+    /// Key the Texture was Uploaded with.
+    pub key: U,
+    /// Extra data stored with the [`crate::Allocation`].
+    pub data: Data,
+    /// Texture Atlas Array Layer the Texture is within.
+    pub layer: usize,
+    /// Id of the Allocation within the layer's allocator.
+    pub alloc_id: guillotiere::AllocId,
+    /// x, y, width and height of the Texture within the layer.
+    pub rect: [u32; 4],
+    /// Reference count of the Texture within the [`AtlasSet`] cache.
+    pub ref_count: usize,
+}
+
+/// A single Texture layer stored within an [`AtlasCache`].
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AtlasCacheLayer {
+    /// Allocator state of the layer so its free space is restored as is.
+    pub allocator: Allocator,
+    /// Tightly packed bytes of the whole layer in the [`AtlasSet`]'s format.
+    pub bytes: Vec<u8>,
+}
+
+/// Serializable Snapshot of an [`AtlasSet`]'s Textures and layout.
+/// Created with [`AtlasSet::export_cache`] and restored with [`AtlasSet::import_cache`].
+/// Used to skip decoding every image again on startup.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AtlasCache<U, Data> {
+    /// Width and Height of the [`AtlasSet`] that was exported.
+    pub size: u32,
+    /// Bytes per pixel of the [`AtlasSet`]'s format that was exported.
+    pub bytes_per_pixel: u32,
+    /// Every layer of the [`AtlasSet`] that was exported.
+    pub layers: Vec<AtlasCacheLayer>,
+    /// All the Textures that were stored within the [`AtlasSet`], least
+    /// recently used first.
+    pub entries: Vec<AtlasCacheEntry<U, Data>>,
+}
+
+impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
+    /// Returns the bytes per pixel of the [`AtlasSet`]'s format.
+    ///
+    pub(crate) fn bytes_per_pixel(&self) -> u32 {
+        self.format.block_copy_size(None).unwrap_or(4)
+    }
+
+    /// Reads back every texture layer from the GPU.
+    /// This Blocks until the GPU has finished copying the layers.
+    /// Returns each layer as tightly packed rows of bytes.
+    ///
+    pub(crate) fn read_layers(
+        &self,
+        renderer: &GpuRenderer,
+    ) -> Result<Vec<Vec<u8>>, GraphicsError> {
+        let bytes_per_pixel = self.bytes_per_pixel();
+        let row_size = self.size * bytes_per_pixel;
+        let padded_row_size = row_size
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let layer_count = (self.layers.len() as u32)
+            .min(self.texture.depth_or_array_layers());
+        let layer_size = padded_row_size as u64 * self.size as u64;
+
+        let buffer = renderer.device().create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Atlas readback buffer"),
+            size: layer_size * layer_count as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = renderer.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("Atlas readback encoder"),
+            },
+        );
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: Some(self.size),
+                },
+            },
+            wgpu::Extent3d {
+                width: self.size,
+                height: self.size,
+                depth_or_array_layers: layer_count,
+            },
+        );
+
+        renderer.queue().submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let _ = renderer.device().poll(wgpu::Maintain::Wait);
+
+        receiver
+            .recv()
+            .map_err(|_| {
+                OtherError::new("Atlas readback was dropped before mapping.")
+            })?
+            .map_err(|_| {
+                OtherError::new("Failed to map the Atlas readback.")
+            })?;
+
+        let layers = {
+            let mapped = slice.get_mapped_range();
+
+            (0..layer_count as usize)
+                .map(|layer| {
+                    let start = layer * layer_size as usize;
+
+                    mapped[start..start + layer_size as usize]
+                        .chunks(padded_row_size as usize)
+                        .flat_map(|row| &row[..row_size as usize])
+                        .copied()
+                        .collect()
+                })
+                .collect()
+        };
+
+        buffer.unmap();
+        Ok(layers)
+    }
+
+    /// Exports all the Textures, their keys and the layout of every layer
+    /// into a serializable [`AtlasCache`].
+    /// This reads the texture back from the GPU and Blocks until it is done.
+    ///
+    pub fn export_cache(
+        &self,
+        renderer: &GpuRenderer,
+    ) -> Result<AtlasCache<U, Data>, GraphicsError> {
+        let bytes = self.read_layers(renderer)?;
+
+        if bytes.len() != self.layers.len() {
+            return Err(GraphicsError::Other(OtherError::new(
+                "Atlas layers are missing from the Texture.",
+            )));
+        }
+
+        let layers = self
+            .layers
+            .iter()
+            .zip(bytes)
+            .map(|(layer, bytes)| AtlasCacheLayer {
+                allocator: layer.allocator.clone(),
+                bytes,
+            })
+            .collect();
+
+        // Least recently used first so importing restores the same order.
+        let mut ids: Vec<usize> =
+            self.cache.iter().rev().map(|(id, _)| *id).collect();
+        ids.extend(
+            self.store
+                .iter()
+                .map(|(id, _)| id)
+                .filter(|id| !self.cache.contains(id)),
+        );
+
+        let entries = ids
+            .into_iter()
+            .filter_map(|id| {
+                let (allocation, key) = self.store.get(id)?;
+                let (x, y, width, height) = allocation.rect();
+
+                Some(AtlasCacheEntry {
+                    id,
+                    key: key.clone(),
+                    data: allocation.data,
+                    layer: allocation.layer,
+                    alloc_id: allocation.allocation.id,
+                    rect: [x, y, width, height],
+                    ref_count: self.cache.peek(&id).copied().unwrap_or(1),
+                })
+            })
+            .collect();
+
+        Ok(AtlasCache {
+            size: self.size,
+            bytes_per_pixel: self.bytes_per_pixel(),
+            layers,
+            entries,
+        })
+    }
+
+    /// Replaces the [`AtlasSet`]'s Textures with the ones in the [`AtlasCache`].
+    /// Every layer is restored with the exported allocator state and bytes
+    /// in one upload per layer, so each [`crate::TextureId`] and
+    /// [`crate::Allocation`] is the same as when it was exported.
+    /// Returns an Error and leaves the [`AtlasSet`] untouched if the cache
+    /// does not match the [`AtlasSet`] or its layout is invalid.
+    ///
+    pub fn import_cache(
+        &mut self,
+        renderer: &GpuRenderer,
+        cache: AtlasCache<U, Data>,
+    ) -> Result<(), GraphicsError> {
+        let bytes_per_pixel = self.bytes_per_pixel();
+        let layer_bytes =
+            self.size as usize * self.size as usize * bytes_per_pixel as usize;
+
+        if cache.bytes_per_pixel != bytes_per_pixel {
+            return Err(GraphicsError::Other(OtherError::new(
+                "AtlasCache format does not match the AtlasSet format.",
+            )));
+        }
+
+        if cache.size != self.size {
+            return Err(GraphicsError::Other(OtherError::new(
+                "AtlasCache size does not match the AtlasSet size.",
+            )));
+        }
+
+        if cache.layers.len() > self.max_layers {
+            return Err(GraphicsError::AtlasMaxLayers);
+        }
+
+        if cache
+            .layers
+            .iter()
+            .any(|layer| layer.bytes.len() != layer_bytes)
+        {
+            return Err(GraphicsError::Other(OtherError::new(
+                "AtlasCache layer bytes do not match the layer size.",
+            )));
+        }
+
+        let mut store = Vec::with_capacity(cache.entries.len());
+
+        for entry in &cache.entries {
+            let [x, y, width, height] = entry.rect;
+
+            if entry.layer >= cache.layers.len()
+                || x.saturating_add(width) > self.size
+                || y.saturating_add(height) > self.size
+            {
+                return Err(GraphicsError::Other(OtherError::new(
+                    "AtlasCache entry is outside of its layer.",
+                )));
+            }
+
+            let allocation = Allocation {
+                allocation: guillotiere::Allocation {
+                    id: entry.alloc_id,
+                    rectangle: guillotiere::Rectangle::new(
+                        guillotiere::point2(x as i32, y as i32),
+                        guillotiere::point2(
+                            (x + width) as i32,
+                            (y + height) as i32,
+                        ),
+                    ),
+                },
+                layer: entry.layer,
+                data: entry.data,
+            };
+
+            store.push((entry.id, (allocation, entry.key.clone())));
+        }
+
+        let store: Slab<_> = store.into_iter().collect();
+
+        if store.len() != cache.entries.len()
+            || store
+                .iter()
+                .map(|(_, (_, key))| key)
+                .collect::<AHashSet<_>>()
+                .len()
+                != store.len()
+        {
+            return Err(GraphicsError::Other(OtherError::new(
+                "AtlasCache has duplicate Texture ids or keys.",
+            )));
+        }
+
+        self.clear();
+
+        let layer_count = cache.layers.len() as u32;
+
+        if layer_count > self.texture.depth_or_array_layers() {
+            self.rebuild_texture(layer_count, 0, renderer);
+        }
+
+        self.layers = Vec::with_capacity(cache.layers.len());
+
+        for (i, layer) in cache.layers.into_iter().enumerate() {
+            renderer.queue().write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &self.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: i as u32,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &layer.bytes,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(self.size * bytes_per_pixel),
+                    rows_per_image: Some(self.size),
+                },
+                wgpu::Extent3d {
+                    width: self.size,
+                    height: self.size,
+                    depth_or_array_layers: 1,
+                },
+            );
+
+            self.layers.push(Atlas::from_allocator(layer.allocator));
+        }
+
+        for entry in cache.entries {
+            self.layers[entry.layer].insert_index(entry.id);
+            self.lookup.insert(entry.key, entry.id);
+            self.cache.push(entry.id, entry.ref_count.max(1));
+        }
+
+        self.store = store;
+        Ok(())
+    }
+}
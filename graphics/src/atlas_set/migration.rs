@@ -9,6 +9,8 @@ pub struct MigrationTask {
     pub migrating: Vec<usize>,
     /// Textures that dont need migration and Have extra space to try against.
     pub avaliable: Vec<usize>,
+    /// Amount of layers marked for migration when the task was created.
+    pub total: usize,
 }
 
 impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
@@ -16,7 +18,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         &mut self,
         task: &mut MigrationTask,
     ) -> Result<usize, GraphicsError> {
-        if self.layers.len() + 1 == self.max_layers {
+        if self.layers.len() >= self.max_layers {
             return Err(GraphicsError::AtlasMaxLayers);
         }
 
@@ -46,7 +48,7 @@ impl<U: Hash + Eq + Clone, Data: Copy + Default> AtlasSet<U, Data> {
         // are working with in this round.
         if let Some(layer) = self.layers.get_mut(migrating_layer_id) {
             for alloc_id in layer.allocated.clone() {
-                if let Some((allocation, _hash)) = self.peek(alloc_id) {
+                if let Some((allocation, _hash)) = self.store.get(alloc_id) {
                     migrating.push((alloc_id, *allocation));
                 }
             }
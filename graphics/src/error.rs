@@ -21,6 +21,18 @@ impl OtherError {
     }
 }
 
+/// Reasons an [`crate::AtlasSet`] upload can fail.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Error)]
+pub enum AtlasUploadError {
+    #[error("Texture is larger than the atlas layer size of {max}.")]
+    TooLarge { max: u32 },
+    #[error("Atlas has no more space and can not add more layers.")]
+    Full,
+    #[error("Texture width and height must be more than 0.")]
+    InvalidSize,
+}
+
 #[derive(Debug, Error)]
 pub enum GraphicsError {
     #[error(transparent)]
@@ -47,8 +59,37 @@ pub enum GraphicsError {
     EventLoopExternal(#[from] winit::error::ExternalError),
     #[error(transparent)]
     OsError(#[from] winit::error::OsError),
+    #[error(transparent)]
+    BadIcon(#[from] winit::window::BadIcon),
     #[error("Could not Defragment the Textures due to Failure in Wgpu or other issues.")]
     DefragFailed,
     #[error("The Atlas already has max layers and can not hold anymore.")]
     AtlasMaxLayers,
+    #[error("The Atlas needs {needed} but the device only allows {max}.")]
+    AtlasLimitExceeded { needed: u32, max: u32 },
+    #[error("The Atlas size {size} is below the minimum of {min}.")]
+    AtlasSizeTooSmall { size: u32, min: u32 },
+    #[error(transparent)]
+    AtlasUpload(#[from] AtlasUploadError),
+    #[error("Got {got} points but at least {needed} are needed.")]
+    TooFewPoints { got: usize, needed: usize },
+    #[error("Got {len} points but triangles need a multiple of 3.")]
+    InvalidPointCount { len: usize },
+    #[error("Failed to upload the Texture into the Atlas.")]
+    TextureUploadFailed,
+    #[error("Frame does not Exist. Did you forget to update the renderer?")]
+    FrameNotAcquired,
+    #[error("The Surface does not support any Texture formats.")]
+    NoSurfaceFormat,
+    #[error("The Surface does not support the alpha mode {0:?}.")]
+    UnsupportedAlphaMode(wgpu::CompositeAlphaMode),
+    #[error("The format {0:?} is not a depth or depth stencil format.")]
+    InvalidDepthFormat(wgpu::TextureFormat),
+    #[error(
+        "The depth format {format:?} needs the device features {features:?}."
+    )]
+    MissingDepthFeatures {
+        format: wgpu::TextureFormat,
+        features: wgpu::Features,
+    },
 }
@@ -1,10 +1,14 @@
+mod measure;
 mod pipeline;
+mod pool;
 mod render;
 mod text;
 mod vertex;
 
 pub use cosmic_text::Shaping;
-pub use pipeline::TextRenderPipeline;
+pub use measure::*;
+pub use pipeline::{TextGammaRenderPipeline, TextRenderPipeline};
+pub use pool::*;
 pub use render::*;
 pub use text::*;
 pub use vertex::*;
@@ -0,0 +1,115 @@
+use crate::{TextOptions, Vec2};
+use cosmic_text::{Attrs, Buffer, FontSystem, Metrics};
+
+/// Size and location of a single shaped glyph from
+/// [`TextMeasurer::measure_glyphs`].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GlyphMeasure {
+    /// Line the glyph is on.
+    pub line: usize,
+    /// Start byte index of the glyph within its line.
+    pub start: usize,
+    /// End byte index of the glyph within its line.
+    pub end: usize,
+    /// X and top Y of the glyph from the top left of the text.
+    pub pos: Vec2,
+    /// Advance width and line height of the glyph.
+    pub size: Vec2,
+}
+
+/// Measures strings with one reused cosmic text [`Buffer`] so measuring
+/// does not allocate a new [`Buffer`] each call.
+///
+pub struct TextMeasurer {
+    /// Cosmic Text [`Buffer`] reused for every measurement.
+    pub buffer: Buffer,
+}
+
+impl TextMeasurer {
+    /// Creates a new [`TextMeasurer`].
+    ///
+    pub fn new(font_system: &mut FontSystem, metrics: Metrics) -> Self {
+        Self {
+            buffer: Buffer::new(font_system, metrics),
+        }
+    }
+
+    /// Shapes the text into the reused [`Buffer`] using the options.
+    ///
+    fn shape(
+        &mut self,
+        font_system: &mut FontSystem,
+        text: &str,
+        attrs: Attrs,
+        options: &TextOptions,
+    ) {
+        let metrics = options
+            .metrics
+            .unwrap_or(Metrics::new(16.0, 16.0).scale(options.scale));
+
+        self.buffer.set_metrics(font_system, metrics);
+        self.buffer.set_wrap(font_system, options.wrap);
+        self.buffer.set_size(
+            font_system,
+            options.buffer_width,
+            options.buffer_height,
+        );
+        self.buffer
+            .set_text(font_system, text, attrs, options.shaping);
+    }
+
+    /// Returns how big the String will be when Rendering.
+    /// Same as [`crate::Text::measure_string`] without a new [`Buffer`].
+    ///
+    pub fn measure_string(
+        &mut self,
+        font_system: &mut FontSystem,
+        text: &str,
+        attrs: Attrs,
+        options: TextOptions,
+    ) -> Vec2 {
+        self.shape(font_system, text, attrs, &options);
+
+        let (width, total_lines) = self.buffer.layout_runs().fold(
+            (0.0, 0usize),
+            |(width, total_lines), run| {
+                (run.line_w.max(width), total_lines + 1)
+            },
+        );
+
+        let (max_width, max_height) = self.buffer.size();
+        let height = total_lines as f32 * self.buffer.metrics().line_height;
+
+        Vec2::new(
+            width.min(max_width.unwrap_or(0.0).max(width)),
+            height.min(max_height.unwrap_or(0.0).max(height)),
+        )
+    }
+
+    /// Returns the size and location of every glyph in the String.
+    /// The String is shaped once and the glyphs are read from the layout.
+    ///
+    pub fn measure_glyphs(
+        &mut self,
+        font_system: &mut FontSystem,
+        text: &str,
+        attrs: Attrs,
+        options: TextOptions,
+    ) -> Vec<GlyphMeasure> {
+        self.shape(font_system, text, attrs, &options);
+
+        self.buffer
+            .layout_runs()
+            .flat_map(|run| {
+                run.glyphs.iter().map(move |glyph| GlyphMeasure {
+                    line: run.line_i,
+                    start: glyph.start,
+                    end: glyph.end,
+                    pos: Vec2::new(glyph.x, run.line_top),
+                    size: Vec2::new(glyph.w, run.line_height),
+                })
+            })
+            .collect()
+    }
+}
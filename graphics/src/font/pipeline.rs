@@ -17,84 +17,113 @@ impl PipeLineLayout for TextRenderPipeline {
         layouts: &mut LayoutStorage,
         surface_format: wgpu::TextureFormat,
     ) -> wgpu::RenderPipeline {
-        let shader = gpu_device.device().create_shader_module(
-            wgpu::ShaderModuleDescriptor {
-                label: Some("Shader"),
-                source: wgpu::ShaderSource::Wgsl(
-                    include_str!("../shaders/textshader.wgsl").into(),
-                ),
-            },
-        );
+        create_text_pipeline(gpu_device, layouts, surface_format, "fragment")
+    }
+}
 
-        let system_layout = layouts.create_layout(gpu_device, SystemLayout);
-        let texture_layout = layouts.create_layout(gpu_device, TextureLayout);
+/// [`crate::Text`] RenderPipeline Layout that applies the glyph coverage
+/// in linear space for gamma correct blending on sRGB surfaces.
+///
+#[repr(C)]
+#[derive(Clone, Copy, Hash, Pod, Zeroable)]
+pub struct TextGammaRenderPipeline;
 
-        // Create the render pipeline.
-        gpu_device.device().create_render_pipeline(
-            &wgpu::RenderPipelineDescriptor {
-                label: Some("Text render pipeline"),
-                layout: Some(&gpu_device.device().create_pipeline_layout(
-                    &wgpu::PipelineLayoutDescriptor {
-                        label: Some("Text_render_pipeline_layout"),
-                        bind_group_layouts: &[
-                            &system_layout,
-                            &texture_layout,
-                            &texture_layout,
-                        ],
-                        push_constant_ranges: &[],
-                    },
-                )),
-                vertex: wgpu::VertexState {
-                    module: &shader,
-                    entry_point: Some("vertex"),
-                    buffers: &[
-                        wgpu::VertexBufferLayout {
-                            array_stride: StaticVertexBuffer::stride(),
-                            step_mode: wgpu::VertexStepMode::Vertex,
-                            attributes: &[
-                                StaticVertexBuffer::vertex_attribute(),
-                            ],
-                        },
-                        wgpu::VertexBufferLayout {
-                            array_stride: TextVertex::stride() as u64,
-                            step_mode: wgpu::VertexStepMode::Instance,
-                            attributes: &TextVertex::attributes(),
-                        },
+impl PipeLineLayout for TextGammaRenderPipeline {
+    fn create_layout(
+        &self,
+        gpu_device: &mut GpuDevice,
+        layouts: &mut LayoutStorage,
+        surface_format: wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline {
+        create_text_pipeline(
+            gpu_device,
+            layouts,
+            surface_format,
+            "fragment_gamma",
+        )
+    }
+}
+
+fn create_text_pipeline(
+    gpu_device: &mut GpuDevice,
+    layouts: &mut LayoutStorage,
+    surface_format: wgpu::TextureFormat,
+    fragment_entry: &str,
+) -> wgpu::RenderPipeline {
+    let shader = gpu_device.device().create_shader_module(
+        wgpu::ShaderModuleDescriptor {
+            label: Some("Shader"),
+            source: wgpu::ShaderSource::Wgsl(crate::load_shader(
+                "textshader.wgsl",
+                include_str!("../shaders/textshader.wgsl"),
+            )),
+        },
+    );
+
+    let system_layout = layouts.create_layout(gpu_device, SystemLayout);
+    let texture_layout = layouts.create_layout(gpu_device, TextureLayout);
+
+    // Create the render pipeline.
+    gpu_device.device().create_render_pipeline(
+        &wgpu::RenderPipelineDescriptor {
+            label: Some("Text render pipeline"),
+            layout: Some(&gpu_device.device().create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("Text_render_pipeline_layout"),
+                    bind_group_layouts: &[
+                        &system_layout,
+                        &texture_layout,
+                        &texture_layout,
                     ],
-                    compilation_options: Default::default(),
+                    push_constant_ranges: &[],
                 },
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    strip_index_format: None,
-                    front_face: wgpu::FrontFace::Ccw,
-                    cull_mode: None,
-                    unclipped_depth: false,
-                    polygon_mode: wgpu::PolygonMode::Fill,
-                    conservative: false,
-                },
-                depth_stencil: Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth32Float,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::LessEqual,
-                    stencil: wgpu::StencilState::default(),
-                    bias: wgpu::DepthBiasState::default(),
-                }),
-                multisample: wgpu::MultisampleState::default(),
-                fragment: Some(wgpu::FragmentState {
-                    module: &shader,
-                    entry_point: Some("fragment"),
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: surface_format,
-                        blend: Some(
-                            wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
-                        ),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                    compilation_options: Default::default(),
-                }),
-                multiview: None,
-                cache: None,
+            )),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vertex"),
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: StaticVertexBuffer::stride(),
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &[StaticVertexBuffer::vertex_attribute()],
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: TextVertex::stride() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &TextVertex::attributes(),
+                    },
+                ],
+                compilation_options: Default::default(),
             },
-        )
-    }
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: gpu_device.depth_format(),
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some(fragment_entry),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            multiview: None,
+            cache: None,
+        },
+    )
 }
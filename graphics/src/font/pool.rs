@@ -0,0 +1,87 @@
+use crate::{GpuRenderer, Text, Vec2, Vec3};
+use cosmic_text::Metrics;
+
+/// Recycles [`Text`]'s so short lived text like damage numbers do not
+/// allocate a new cosmic text buffer and store buffer each time.
+///
+pub struct TextPool {
+    /// [`Text`]'s waiting to be reused.
+    pub free: Vec<Text>,
+    /// Max amount of [`Text`]'s the pool keeps. Extra released
+    /// [`Text`]'s are unloaded instead.
+    pub max_size: usize,
+}
+
+impl TextPool {
+    /// Creates a new [`TextPool`].
+    ///
+    /// # Arguments
+    /// - max_size: Max amount of [`Text`]'s to keep for reuse.
+    ///
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            free: Vec::new(),
+            max_size,
+        }
+    }
+
+    /// Returns a cleared [`Text`] reusing a pooled one when possible.
+    /// Content, scroll, cursor and bounds are reset like [`Text::new`].
+    ///
+    pub fn acquire(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        metrics: Option<Metrics>,
+        pos: Vec3,
+        size: Vec2,
+        scale: f32,
+        render_layer: u32,
+    ) -> Text {
+        match self.free.pop() {
+            Some(mut text) => {
+                text.reset(renderer, metrics, pos, size, scale, render_layer);
+                text
+            }
+            None => {
+                Text::new(renderer, metrics, pos, size, scale, render_layer)
+            }
+        }
+    }
+
+    /// Returns a [`Text`] to the pool without unloading its store buffer.
+    /// If the pool is full the [`Text`] is unloaded instead.
+    ///
+    pub fn release(&mut self, renderer: &mut GpuRenderer, text: Text) {
+        if self.free.len() < self.max_size {
+            self.free.push(text);
+        } else {
+            text.unload(renderer);
+        }
+    }
+
+    /// Returns the amount of [`Text`]'s waiting to be reused.
+    ///
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Returns true if no [`Text`]'s are waiting to be reused.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// Unloads all pooled [`Text`]'s.
+    ///
+    pub fn clear(&mut self, renderer: &mut GpuRenderer) {
+        for text in self.free.drain(..) {
+            text.unload(renderer);
+        }
+    }
+}
+
+impl Default for TextPool {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
@@ -1,18 +1,34 @@
 use crate::{
-    AsBufferPass, AtlasSet, GpuRenderer, GraphicsError, InstanceBuffer,
-    OrderedIndex, SetBuffers, StaticVertexBuffer, Text, TextRenderPipeline,
-    TextVertex, Vec2,
+    Allocation, AsBufferPass, AtlasSet, AtlasUploadError, BufferLayout,
+    DrawOrder, GpuRenderer, GraphicsError, Index, InstanceBuffer, OrderedIndex,
+    SetBuffers, StaticVertexBuffer, Text, TextGammaRenderPipeline,
+    TextRenderPipeline, TextVertex, Vec2,
 };
 use cosmic_text::{CacheKey, SwashCache};
 use log::{error, warn};
+use std::ops::Range;
+
+/// Instances within a buffer layer that all use the same text [`AtlasSet`].
+///
+pub(crate) struct TextBatch {
+    /// Range of the instances within the instance buffer.
+    pub(crate) instances: Range<u32>,
+    /// Index of the text AtlasSet. None if only Emoji or backgrounds.
+    pub(crate) atlas: Option<usize>,
+}
 
 /// [`Text`] text and Emoji AtlasSet holder.
 ///
 pub struct TextAtlas {
-    /// AtlasSet holding data from Text only.
-    pub(crate) text: AtlasSet<CacheKey, Vec2>,
+    /// AtlasSet's holding data from Text only.
+    /// A new one is added when all others are full.
+    pub(crate) text: Vec<AtlasSet<CacheKey, Vec2>>,
     /// AtlasSet holding data from Colored Emoji's only.
     pub(crate) emoji: AtlasSet<CacheKey, Vec2>,
+    /// Size used to create more text AtlasSet's.
+    size: u32,
+    /// Max amount of text AtlasSet's that can be created. Default is 4.
+    pub max_text_atlases: usize,
 }
 
 impl TextAtlas {
@@ -27,34 +43,112 @@ impl TextAtlas {
         size: u32,
     ) -> Result<Self, GraphicsError> {
         Ok(Self {
-            text: AtlasSet::new(
+            text: vec![AtlasSet::new(
                 renderer,
                 wgpu::TextureFormat::R8Unorm,
                 false,
                 size,
-            ),
+            )],
             emoji: AtlasSet::new(
                 renderer,
                 wgpu::TextureFormat::Rgba8UnormSrgb,
                 false,
                 size,
             ),
+            size,
+            max_text_atlases: 4,
         })
     }
 
-    /// Calles Trim on both internal [`AtlasSet`]'s
+    /// Calles Trim on all internal [`AtlasSet`]'s
     ///
     pub fn trim(&mut self) {
         self.emoji.trim();
-        self.text.trim();
+
+        for text in &mut self.text {
+            text.trim();
+        }
+    }
+
+    /// Returns the amount of text [`AtlasSet`]'s in use.
+    ///
+    pub fn text_atlas_count(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Returns the GPU memory in bytes used by all the text and emoji
+    /// [`AtlasSet`]'s.
+    ///
+    pub fn gpu_bytes(&self) -> u64 {
+        self.text.iter().map(|text| text.gpu_bytes()).sum::<u64>()
+            + self.emoji.gpu_bytes()
+    }
+
+    /// Looks up a glyph in all text [`AtlasSet`]'s.
+    /// Returns the text AtlasSet's index and the [`Allocation`].
+    ///
+    pub(crate) fn get_text_by_key(
+        &mut self,
+        key: &CacheKey,
+    ) -> Option<(u32, Allocation<Vec2>)> {
+        self.text.iter_mut().enumerate().find_map(|(i, text)| {
+            text.get_by_key(key).map(|alloc| (i as u32, alloc))
+        })
+    }
+
+    /// Uploads a glyph to the first text [`AtlasSet`] with space.
+    /// Creates a new text AtlasSet when all are full and max_text_atlases
+    /// is not reached. Returns the text AtlasSet's index and the [`Allocation`].
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn upload_text(
+        &mut self,
+        key: CacheKey,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+        data: Vec2,
+        renderer: &mut GpuRenderer,
+    ) -> Result<(u32, Allocation<Vec2>), GraphicsError> {
+        for (i, text) in self.text.iter_mut().enumerate() {
+            match text.try_upload_with_alloc(
+                key, bytes, width, height, data, renderer,
+            ) {
+                Ok((_, allocation)) => return Ok((i as u32, allocation)),
+                Err(AtlasUploadError::Full) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        if self.text.len() >= self.max_text_atlases {
+            return Err(GraphicsError::AtlasFull);
+        }
+
+        let index = self.text.len() as u32;
+        let mut text = AtlasSet::new(
+            renderer,
+            wgpu::TextureFormat::R8Unorm,
+            false,
+            self.size,
+        );
+        let (_, allocation) = text
+            .try_upload_with_alloc(key, bytes, width, height, data, renderer)?;
+        self.text.push(text);
+        Ok((index, allocation))
     }
 }
 
 /// Instance Buffer Setup for [`Text`].
+/// Glyphs are rasterized without the swash image cache and only kept in the
+/// [`TextAtlas`], so [`TextAtlas::trim`] is all that is needed to free them.
 ///
 pub struct TextRenderer {
     pub(crate) buffer: InstanceBuffer<TextVertex>,
+    /// Instances of each buffer layer split by text [`AtlasSet`] in DrawOrder.
+    pub(crate) batches: Vec<Vec<TextBatch>>,
     pub(crate) swash_cache: SwashCache,
+    /// If the glyph coverage should be blended in linear space.
+    pub(crate) gamma_correct: bool,
 }
 
 impl TextRenderer {
@@ -63,10 +157,27 @@ impl TextRenderer {
     pub fn new(renderer: &GpuRenderer) -> Result<Self, GraphicsError> {
         Ok(Self {
             buffer: InstanceBuffer::new(renderer.gpu_device(), 1024),
+            batches: Vec::new(),
             swash_cache: SwashCache::new(),
+            gamma_correct: false,
         })
     }
 
+    /// Sets if the glyph coverage should be applied in linear space using
+    /// [`TextGammaRenderPipeline`]. This gives gamma correct blending on sRGB
+    /// surfaces which stops light on dark text from looking too thin.
+    ///
+    pub fn set_gamma_correct(&mut self, gamma_correct: bool) -> &mut Self {
+        self.gamma_correct = gamma_correct;
+        self
+    }
+
+    /// Returns if the glyph coverage is applied in linear space.
+    ///
+    pub fn gamma_correct(&self) -> bool {
+        self.gamma_correct
+    }
+
     /// Adds a Buffer [`OrderedIndex`] to the Rendering Store to get processed.
     /// This must be done before [`TextRenderer::finalize`] but after [`Text::update`] in order for it to Render.
     ///
@@ -85,9 +196,42 @@ impl TextRenderer {
 
     /// Finalizes the Buffer by processing staged [`OrderedIndex`]'s and uploading it to the GPU.
     /// Must be called after all the [`TextRenderer::add_buffer_store`]'s.
+    /// Each buffer layer is split into batches of glyphs that use the same
+    /// text [`AtlasSet`] so they keep their DrawOrder.
     ///
     pub fn finalize(&mut self, renderer: &mut GpuRenderer) {
+        for processing in &mut self.buffer.unprocessed {
+            processing.sort();
+        }
+
+        let batches: Vec<Vec<TextBatch>> = self
+            .buffer
+            .unprocessed
+            .iter()
+            .map(|processing| {
+                text_batches(processing.iter().filter_map(|index| {
+                    renderer
+                        .get_buffer(index.index)
+                        .map(|store| store.store.as_slice())
+                }))
+            })
+            .collect();
+
         self.buffer.finalize(renderer);
+        self.batches.clear();
+
+        for (mut layer, details) in
+            batches.into_iter().zip(&self.buffer.buffers)
+        {
+            let start = details.as_ref().map_or(0, |details| details.start);
+
+            for batch in &mut layer {
+                batch.instances =
+                    batch.instances.start + start..batch.instances.end + start;
+            }
+
+            self.batches.push(layer);
+        }
     }
 
     /// Updates a [`Text`] and adds its [`TextOrderedIndex`] to staging using [`TextRenderer::add_buffer_store`].
@@ -111,6 +255,19 @@ impl TextRenderer {
         Ok(())
     }
 
+    /// Enables or Disables gathering the sorted render order during finalize.
+    ///
+    pub fn set_debug_order(&mut self, enable: bool) {
+        self.buffer.set_debug_order(enable);
+    }
+
+    /// Returns the sorted (buffer_layer, [`Index`], [`DrawOrder`]) list from the last
+    /// [`TextRenderer::finalize`]. Is empty unless enabled with [`TextRenderer::set_debug_order`].
+    ///
+    pub fn debug_order(&self) -> &[(usize, Index, DrawOrder)] {
+        self.buffer.debug_order()
+    }
+
     /// [`Text`] does not use Scissor Clipping.
     /// It uses its own Internal Bounds Clipper.
     ///
@@ -133,6 +290,16 @@ where
         atlas: &'b TextAtlas,
         buffer_layer: usize,
     );
+
+    /// Renders every buffer layer of [`Text`]'s that have been processed and finalized.
+    /// Shared pipeline and bind state is only set once.
+    ///
+    fn render_all_text(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b TextRenderer,
+        atlas: &'b TextAtlas,
+    );
 }
 
 impl<'a, 'b> RenderText<'a, 'b> for wgpu::RenderPass<'a>
@@ -151,21 +318,157 @@ where
             return;
         }
 
-        if let Some(Some(details)) = buffer.buffer.buffers.get(buffer_layer) {
-            if buffer.buffer.count() > 0 {
+        if let Some(batches) = buffer.batches.get(buffer_layer) {
+            if buffer.buffer.count() > 0 && !batches.is_empty() {
+                self.set_buffers(renderer.buffer_object.as_buffer_pass());
+                self.set_bind_group(2, atlas.emoji.bind_group(), &[]);
+                self.set_vertex_buffer(1, buffer.buffer.instances(None));
+                self.set_pipeline(if buffer.gamma_correct {
+                    renderer.get_pipelines(TextGammaRenderPipeline).unwrap()
+                } else {
+                    renderer.get_pipelines(TextRenderPipeline).unwrap()
+                });
+
+                draw_text_batches(self, atlas, batches);
+            }
+        }
+    }
+
+    fn render_all_text(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b TextRenderer,
+        atlas: &'b TextAtlas,
+    ) {
+        if buffer.buffer.is_clipped() {
+            error!("Text uses its own clipping mechanisim it does not need to be clipped by the clipper.");
+            return;
+        }
+
+        if buffer.buffer.count() == 0 {
+            return;
+        }
+
+        let mut state_set = false;
+
+        for batches in buffer.batches.iter().filter(|b| !b.is_empty()) {
+            if !state_set {
                 self.set_buffers(renderer.buffer_object.as_buffer_pass());
-                self.set_bind_group(1, atlas.text.bind_group(), &[]);
                 self.set_bind_group(2, atlas.emoji.bind_group(), &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
-                self.set_pipeline(
-                    renderer.get_pipelines(TextRenderPipeline).unwrap(),
-                );
-                self.draw_indexed(
-                    0..StaticVertexBuffer::index_count(),
-                    0,
-                    details.start..details.end,
-                );
+                self.set_pipeline(if buffer.gamma_correct {
+                    renderer.get_pipelines(TextGammaRenderPipeline).unwrap()
+                } else {
+                    renderer.get_pipelines(TextRenderPipeline).unwrap()
+                });
+                state_set = true;
             }
+
+            draw_text_batches(self, atlas, batches);
         }
     }
 }
+
+/// Draws each [`TextBatch`] with its text [`AtlasSet`] bound.
+fn draw_text_batches<'a>(
+    pass: &mut wgpu::RenderPass<'a>,
+    atlas: &'a TextAtlas,
+    batches: &[TextBatch],
+) {
+    for batch in batches {
+        let Some(text) = atlas.text.get(batch.atlas.unwrap_or(0)) else {
+            continue;
+        };
+
+        pass.set_bind_group(1, text.bind_group(), &[]);
+        pass.draw_indexed(
+            0..StaticVertexBuffer::index_count(),
+            0,
+            batch.instances.clone(),
+        );
+    }
+}
+
+/// Splits the instance bytes of a buffer layer's sorted stores into batches
+/// of glyphs using the same text [`AtlasSet`]. Emoji and backgrounds do not
+/// sample a text AtlasSet so they join whichever batch they are in.
+/// Instance ranges start from 0 for the buffer layer.
+fn text_batches<'s>(stores: impl Iterator<Item = &'s [u8]>) -> Vec<TextBatch> {
+    let mut batches: Vec<TextBatch> = Vec::new();
+    let vertices =
+        stores.flat_map(|store| store.chunks_exact(TextVertex::stride()));
+
+    for (count, bytes) in (0u32..).zip(vertices) {
+        let vertex: TextVertex = bytemuck::pod_read_unaligned(bytes);
+        let atlas = (vertex.is_color == 0).then_some(vertex.atlas as usize);
+
+        match batches.last_mut() {
+            Some(batch)
+                if atlas.is_none()
+                    || batch.atlas.is_none()
+                    || batch.atlas == atlas =>
+            {
+                batch.atlas = batch.atlas.or(atlas);
+                batch.instances.end += 1;
+            }
+            _ => batches.push(TextBatch {
+                instances: count..count + 1,
+                atlas,
+            }),
+        }
+    }
+
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(atlas: u32, is_color: u32) -> TextVertex {
+        TextVertex {
+            atlas,
+            is_color,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn batches_split_by_atlas_in_draw_order() {
+        let first = [glyph(0, 0), glyph(0, 0), glyph(1, 0)];
+        let second = [glyph(5, 1), glyph(1, 0), glyph(0, 0)];
+        let third = [glyph(0, TextVertex::BACKGROUND), glyph(2, 0)];
+        let stores: [&[u8]; 3] = [
+            bytemuck::cast_slice(&first),
+            bytemuck::cast_slice(&second),
+            bytemuck::cast_slice(&third),
+        ];
+
+        let batches: Vec<(Range<u32>, Option<usize>)> =
+            text_batches(stores.into_iter())
+                .into_iter()
+                .map(|batch| (batch.instances, batch.atlas))
+                .collect();
+
+        assert_eq!(
+            batches,
+            vec![
+                (0..2, Some(0)),
+                (2..5, Some(1)),
+                (5..7, Some(0)),
+                (7..8, Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn batches_of_only_emoji_have_no_atlas() {
+        let emoji = [glyph(3, 1), glyph(0, TextVertex::BACKGROUND)];
+        let batches =
+            text_batches(std::iter::once(bytemuck::cast_slice(&emoji[..])));
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].instances, 0..2);
+        assert_eq!(batches[0].atlas, None);
+    }
+}
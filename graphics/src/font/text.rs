@@ -1,10 +1,12 @@
 use crate::{
-    Bounds, CameraType, Color, DrawOrder, GpuRenderer, GraphicsError, Index,
-    OrderedIndex, TextAtlas, TextVertex, Vec2, Vec3,
+    AHashMap, Bounds, CameraType, Color, DrawOrder, GpuRenderer, GraphicsError,
+    Index, Mat2, OrderedIndex, TextAtlas, TextMeasurer, TextVertex, Vec2, Vec3,
 };
 use cosmic_text::{
     Attrs, Buffer, Cursor, FontSystem, Metrics, SwashCache, SwashContent, Wrap,
 };
+use std::{borrow::Cow, f32::consts::FRAC_1_SQRT_2};
+use swash::{scale::Source, FontRef};
 
 /// [`Text`] Option Handler for [`Text::measure_string`].
 ///
@@ -64,6 +66,22 @@ pub struct Text {
     pub glyph_vertices: Vec<TextVertex>,
     /// Overides the absolute order values based on position.
     pub order_override: Option<Vec3>,
+    /// Explicit key to sort by instead of the position.
+    pub sort_key: Option<u64>,
+    /// Background colors keyed by the [`Attrs`] metadata of a span.
+    /// Glyphs whose metadata has a color get a background quad behind them.
+    pub background_colors: AHashMap<usize, Color>,
+    /// Skips rounding glyph positions to whole pixels so moving Text
+    /// animates smoothly. Default is false.
+    pub subpixel: bool,
+    /// 2D transform applied to every glyph around transform_pivot.
+    /// Default is identity. Bounds clipping is disabled while transformed.
+    pub transform: Mat2,
+    /// Pivot of the transform relative to pos.
+    pub transform_pivot: Vec2,
+    /// Outline width in pixels and color drawn behind each glyph.
+    /// None by default.
+    pub outline: Option<(f32, Color)>,
     /// If anything got updated we need to update the buffers too.
     pub changed: bool,
 }
@@ -82,10 +100,20 @@ impl Text {
         let mut is_alpha = false;
         let mut width = 0.0;
         let screensize = renderer.size();
-        let bounds_min_x = self.bounds.left.max(0.0);
-        let bounds_min_y = self.bounds.bottom.max(0.0);
-        let bounds_max_x = self.bounds.right.min(screensize.width);
-        let bounds_max_y = self.bounds.top.min(screensize.height);
+        let transformed = self.transform != Mat2::IDENTITY;
+        // Clipping happens before the transform so it is disabled while
+        // transformed to avoid clipping the wrong area.
+        let (bounds_min_x, bounds_min_y, bounds_max_x, bounds_max_y) =
+            if transformed {
+                (f32::MIN, f32::MIN, f32::MAX, f32::MAX)
+            } else {
+                (
+                    self.bounds.left.max(0.0),
+                    self.bounds.bottom.max(0.0),
+                    self.bounds.right.min(screensize.width),
+                    self.bounds.top.min(screensize.height),
+                )
+            };
 
         self.glyph_vertices.clear();
 
@@ -110,87 +138,156 @@ impl Text {
             .skip_while(|run| !is_run_visible(run))
             .take_while(is_run_visible);
 
+        if !self.background_colors.is_empty() {
+            let clip = [bounds_min_x, bounds_min_y, bounds_max_x, bounds_max_y];
+            let background_runs = self
+                .buffer
+                .layout_runs()
+                .skip_while(|run| !is_run_visible(run))
+                .take_while(is_run_visible);
+
+            for run in background_runs {
+                let top = self.pos.y + self.size.y - run.line_top * self.scale;
+                let bottom = top - run.line_height * self.scale;
+                let mut current: Option<(Color, f32, f32)> = None;
+
+                for glyph in run.glyphs.iter() {
+                    let color = self.background_colors.get(&glyph.metadata);
+                    let left = self.pos.x + glyph.x * self.scale;
+                    let right = left + glyph.w * self.scale;
+
+                    // Merge adjacent glyphs that share a color into one quad.
+                    match (&mut current, color) {
+                        (Some((c, _, end)), Some(color)) if c == color => {
+                            *end = right;
+                            continue;
+                        }
+                        _ => {}
+                    }
+
+                    if let Some((c, start, end)) = current.take() {
+                        is_alpha |= Self::push_background_quad(
+                            &mut self.glyph_vertices,
+                            c,
+                            [start, bottom, end, top],
+                            clip,
+                            (self.pos.z, self.camera_type),
+                        );
+                    }
+
+                    current = color.map(|color| (*color, left, right));
+                }
+
+                if let Some((c, start, end)) = current.take() {
+                    is_alpha |= Self::push_background_quad(
+                        &mut self.glyph_vertices,
+                        c,
+                        [start, bottom, end, top],
+                        clip,
+                        (self.pos.z, self.camera_type),
+                    );
+                }
+            }
+        }
+
+        let glyph_start = self.glyph_vertices.len();
+
         for run in layout_runs {
             width = run.line_w.max(width);
 
             for glyph in run.glyphs.iter() {
-                let physical_glyph = glyph.physical(
-                    (self.pos.x, self.pos.y + self.size.y),
-                    self.scale,
-                );
-
-                let (allocation, is_color) = if let Some(allocation) =
-                    atlas.text.get_by_key(&physical_glyph.cache_key)
-                {
-                    (allocation, false)
-                } else if let Some(allocation) =
-                    atlas.emoji.get_by_key(&physical_glyph.cache_key)
-                {
-                    (allocation, true)
+                // In subpixel mode the glyph is snapped relative to the
+                // Text's origin and the fractional origin gets added back.
+                let (offset, origin) = if self.subpixel {
+                    ((0.0, 0.0), (self.pos.x, self.pos.y + self.size.y))
                 } else {
-                    let image = cache
-                        .get_image_uncached(
-                            &mut renderer.font_sys,
-                            physical_glyph.cache_key,
-                        )
-                        .unwrap();
-                    let bitmap = image.data;
-                    let is_color = match image.content {
-                        SwashContent::Color => true,
-                        SwashContent::Mask => false,
-                        SwashContent::SubpixelMask => false,
-                    };
-                    let width = image.placement.width;
-                    let height = image.placement.height;
-
-                    if width > 0 && height > 0 {
-                        if is_color {
-                            let (_, allocation) = atlas
-                                .emoji
-                                .upload_with_alloc(
-                                    physical_glyph.cache_key,
-                                    &bitmap,
-                                    width,
-                                    height,
-                                    Vec2::new(
-                                        image.placement.left as f32,
-                                        image.placement.top as f32,
-                                    ),
-                                    renderer,
-                                )
-                                .ok_or(GraphicsError::AtlasFull)?;
-                            (allocation, is_color)
+                    ((self.pos.x, self.pos.y + self.size.y), (0.0, 0.0))
+                };
+                let physical_glyph = glyph.physical(offset, self.scale);
+                let line_y = if self.subpixel {
+                    run.line_y * self.scale
+                } else {
+                    (run.line_y * self.scale).round()
+                };
+
+                let (allocation, is_color, atlas_id) =
+                    if let Some((atlas_id, allocation)) =
+                        atlas.get_text_by_key(&physical_glyph.cache_key)
+                    {
+                        (allocation, false, atlas_id)
+                    } else if let Some(allocation) =
+                        atlas.emoji.get_by_key(&physical_glyph.cache_key)
+                    {
+                        (allocation, true, 0)
+                    } else {
+                        let image = cache
+                            .get_image_uncached(
+                                &mut renderer.font_sys,
+                                physical_glyph.cache_key,
+                            )
+                            .unwrap();
+                        let bitmap = &image.data;
+                        let is_color = match image.content {
+                            SwashContent::Color => true,
+                            SwashContent::Mask => false,
+                            SwashContent::SubpixelMask => false,
+                        };
+                        let width = image.placement.width;
+                        let height = image.placement.height;
+
+                        if width > 0 && height > 0 {
+                            if is_color {
+                                let (_, allocation) = atlas
+                                    .emoji
+                                    .upload_with_alloc(
+                                        physical_glyph.cache_key,
+                                        &color_glyph_rgba(
+                                            &image,
+                                            raw_color_strike(
+                                                &mut renderer.font_sys,
+                                                &image,
+                                                physical_glyph.cache_key,
+                                            ),
+                                        ),
+                                        width,
+                                        height,
+                                        Vec2::new(
+                                            image.placement.left as f32,
+                                            image.placement.top as f32,
+                                        ),
+                                        renderer,
+                                    )
+                                    .ok_or(GraphicsError::AtlasFull)?;
+                                (allocation, is_color, 0)
+                            } else {
+                                let (atlas_id, allocation) = atlas
+                                    .upload_text(
+                                        physical_glyph.cache_key,
+                                        bitmap,
+                                        width,
+                                        height,
+                                        Vec2::new(
+                                            image.placement.left as f32,
+                                            image.placement.top as f32,
+                                        ),
+                                        renderer,
+                                    )?;
+                                (allocation, is_color, atlas_id)
+                            }
                         } else {
-                            let (_, allocation) = atlas
-                                .text
-                                .upload_with_alloc(
-                                    physical_glyph.cache_key,
-                                    &bitmap,
-                                    width,
-                                    height,
-                                    Vec2::new(
-                                        image.placement.left as f32,
-                                        image.placement.top as f32,
-                                    ),
-                                    renderer,
-                                )
-                                .ok_or(GraphicsError::AtlasFull)?;
-                            (allocation, is_color)
+                            continue;
                         }
-                    } else {
-                        continue;
-                    }
-                };
+                    };
 
                 let position = allocation.data;
                 let (u, v, width, height) = allocation.rect();
                 let (mut u, mut v, mut width, mut height) =
                     (u as f32, v as f32, width as f32, height as f32);
                 let (mut x, mut y) = (
-                    physical_glyph.x as f32 + position.x,
+                    physical_glyph.x as f32 + position.x + origin.0,
                     physical_glyph.y as f32
-                        + ((position.y - height)
-                            - (run.line_y * self.scale).round()),
+                        + origin.1
+                        + ((position.y - height) - line_y),
                 );
                 let color = is_color
                     .then(|| Color::rgba(255, 255, 255, 255))
@@ -245,12 +342,32 @@ impl Text {
                     color: color.0,
                     camera_type: self.camera_type as u32,
                     is_color: is_color as u32,
+                    atlas: atlas_id,
+                    ..Default::default()
                 };
 
                 self.glyph_vertices.push(default);
             }
         }
 
+        if let Some((outline_width, outline_color)) = self.outline {
+            is_alpha |= outline_color.a() < 255;
+            self.push_outline(glyph_start, outline_width, outline_color);
+        }
+
+        if transformed {
+            let transform = self.transform.to_cols_array();
+            let pivot = [
+                self.pos.x + self.transform_pivot.x,
+                self.pos.y + self.transform_pivot.y,
+            ];
+
+            for vertex in &mut self.glyph_vertices {
+                vertex.transform = transform;
+                vertex.pivot = pivot;
+            }
+        }
+
         if let Some(store) = renderer.get_buffer_mut(self.store_id) {
             let bytes: &[u8] = bytemuck::cast_slice(&self.glyph_vertices);
             store.store.resize_with(bytes.len(), || 0);
@@ -263,12 +380,87 @@ impl Text {
             None => self.pos,
         };
 
-        self.order = DrawOrder::new(is_alpha, &order_pos, self.render_layer);
+        self.order = DrawOrder::new(is_alpha, &order_pos, self.render_layer)
+            .with_sort_key(self.sort_key);
         self.changed = false;
 
         Ok(())
     }
 
+    /// Inserts copies of the glyphs starting at glyph_start offset in 8
+    /// directions in the outline color. They get inserted before the glyphs
+    /// so the fill draws over them. Color glyphs are not outlined.
+    ///
+    fn push_outline(&mut self, glyph_start: usize, width: f32, color: Color) {
+        const DIRECTIONS: [(f32, f32); 8] = [
+            (-1.0, 0.0),
+            (1.0, 0.0),
+            (0.0, -1.0),
+            (0.0, 1.0),
+            (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+            (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        ];
+
+        if width <= 0.0 {
+            return;
+        }
+
+        let outlines: Vec<TextVertex> = self.glyph_vertices[glyph_start..]
+            .iter()
+            .filter(|vertex| vertex.is_color == 0)
+            .flat_map(|vertex| {
+                DIRECTIONS.iter().map(move |(x, y)| TextVertex {
+                    position: [
+                        vertex.position[0] + x * width,
+                        vertex.position[1] + y * width,
+                        vertex.position[2],
+                    ],
+                    color: color.0,
+                    ..*vertex
+                })
+            })
+            .collect();
+
+        self.glyph_vertices
+            .splice(glyph_start..glyph_start, outlines);
+    }
+
+    /// Pushes a solid background quad clipped to the given bounds.
+    /// Returns true if the quad's color has alpha.
+    ///
+    fn push_background_quad(
+        vertices: &mut Vec<TextVertex>,
+        color: Color,
+        rect: [f32; 4],
+        clip: [f32; 4],
+        (z, camera_type): (f32, CameraType),
+    ) -> bool {
+        let x = rect[0].max(clip[0]);
+        let y = rect[1].max(clip[1]);
+        let width = rect[2].min(clip[2]) - x;
+        let height = rect[3].min(clip[3]) - y;
+
+        if width <= 0.0 || height <= 0.0 {
+            return false;
+        }
+
+        vertices.push(TextVertex {
+            position: [x, y, z],
+            hw: [width, height],
+            tex_coord: [0.0, 0.0],
+            layer: 0,
+            color: color.0,
+            camera_type: camera_type as u32,
+            is_color: TextVertex::BACKGROUND,
+            atlas: 0,
+            ..Default::default()
+        });
+
+        color.a() < 255
+    }
+
     /// Creates a new [`Text`].
     ///
     pub fn new(
@@ -302,7 +494,13 @@ impl Text {
             scale,
             render_layer,
             order_override: None,
+            sort_key: None,
             glyph_vertices: Vec::new(),
+            background_colors: AHashMap::default(),
+            subpixel: false,
+            transform: Mat2::IDENTITY,
+            transform_pivot: Vec2::ZERO,
+            outline: None,
         }
     }
 
@@ -320,6 +518,15 @@ impl Text {
         renderer.remove_buffer(self.store_id);
     }
 
+    /// Sets an explicit key the [`Text`] sorts by instead of its position.
+    /// Keyed objects draw after unkeyed ones on the same order layer.
+    ///
+    pub fn set_sort_key(&mut self, sort_key: Option<u64>) -> &mut Self {
+        self.changed = true;
+        self.sort_key = sort_key;
+        self
+    }
+
     /// Updates the [`Text`]'s order_override.
     ///
     pub fn set_order_override(
@@ -488,6 +695,62 @@ impl Text {
         self
     }
 
+    /// Sets or removes the background color for spans whose [`Attrs`]
+    /// metadata matches. Adjacent glyphs with the same color are merged
+    /// into one quad which is drawn behind the glyphs.
+    ///
+    /// # Arguments
+    /// - metadata: The metadata set on the span's [`Attrs`].
+    /// - color: Background color or None to remove it.
+    ///
+    pub fn set_background_color(
+        &mut self,
+        metadata: usize,
+        color: Option<Color>,
+    ) -> &mut Self {
+        match color {
+            Some(color) => self.background_colors.insert(metadata, color),
+            None => self.background_colors.remove(&metadata),
+        };
+        self.changed = true;
+        self
+    }
+
+    /// Enables or disables subpixel positioning.
+    /// By default glyphs are snapped to whole pixels which keeps Text crisp,
+    /// but Text moved by fractional amounts will jitter as it crosses pixel
+    /// boundaries. Subpixel positioning moves smoothly at the cost of
+    /// slightly blurrier glyphs, so it is best for animated Text.
+    ///
+    pub fn set_subpixel(&mut self, subpixel: bool) -> &mut Self {
+        self.subpixel = subpixel;
+        self.changed = true;
+        self
+    }
+
+    /// Sets an outline drawn behind the glyphs or None to remove it.
+    /// The outline is made by drawing each glyph 8 more times offset by
+    /// width in the outline color. This is cheap and uses the same atlas
+    /// glyphs but gets uneven at widths over a couple of pixels and draws
+    /// 9 quads per glyph. Outlines may reach width past the clip bounds.
+    ///
+    pub fn set_outline(&mut self, outline: Option<(f32, Color)>) -> &mut Self {
+        self.outline = outline;
+        self.changed = true;
+        self
+    }
+
+    /// Sets a 2D transform such as rotation or shear applied to every glyph
+    /// around the pivot. The pivot is relative to the [`Text`]'s position.
+    /// Bounds clipping is disabled while the transform is not identity.
+    ///
+    pub fn set_transform(&mut self, transform: Mat2, pivot: Vec2) -> &mut Self {
+        self.transform = transform;
+        self.transform_pivot = pivot;
+        self.changed = true;
+        self
+    }
+
     /// Sets the [`Text`]'s default color.
     ///
     pub fn set_default_color(&mut self, color: Color) -> &mut Self {
@@ -522,6 +785,59 @@ impl Text {
         self
     }
 
+    /// Resets the [`Text`] to the same state as [`Text::new`] while keeping
+    /// its cosmic text buffer, vertex allocation and store buffer.
+    /// Used by [`crate::TextPool`] to recycle [`Text`]'s.
+    ///
+    pub fn reset(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        metrics: Option<Metrics>,
+        pos: Vec3,
+        size: Vec2,
+        scale: f32,
+        render_layer: u32,
+    ) -> &mut Self {
+        let font_sys = &mut renderer.font_sys;
+
+        self.buffer.set_metrics_and_size(
+            font_sys,
+            metrics.unwrap_or(Metrics::new(16.0, 16.0).scale(scale)),
+            None,
+            None,
+        );
+        self.buffer.set_wrap(font_sys, Wrap::Word);
+        self.buffer.set_scroll(cosmic_text::Scroll::default());
+        self.buffer.set_text(
+            font_sys,
+            "",
+            cosmic_text::Attrs::new(),
+            cosmic_text::Shaping::Basic,
+        );
+        self.pos = pos;
+        self.size = size;
+        self.scale = scale;
+        self.render_layer = render_layer;
+        self.bounds = Bounds::default();
+        self.order = DrawOrder::default();
+        self.default_color = Color::rgba(0, 0, 0, 255);
+        self.camera_type = CameraType::None;
+        self.cursor = Cursor::default();
+        self.wrap = Wrap::Word;
+        self.line = 0;
+        self.scroll = cosmic_text::Scroll::default();
+        self.order_override = None;
+        self.sort_key = None;
+        self.glyph_vertices.clear();
+        self.background_colors.clear();
+        self.subpixel = false;
+        self.transform = Mat2::IDENTITY;
+        self.transform_pivot = Vec2::ZERO;
+        self.outline = None;
+        self.changed = true;
+        self
+    }
+
     // Used to check and update the vertex array.
     /// Returns a [`OrderedIndex`] used in Rendering.
     ///
@@ -547,6 +863,43 @@ impl Text {
             && mouse_pos[1] < self.pos.y + self.size.y
     }
 
+    /// Converts a screen point into the [`Buffer`]'s layout coordinates
+    /// where y goes down from the top of the [`Text`].
+    fn layout_point(&self, point: Vec2) -> Vec2 {
+        Vec2::new(
+            (point.x - self.pos.x) / self.scale,
+            (self.pos.y + self.size.y - point.y) / self.scale,
+        )
+    }
+
+    /// Hit tests a screen point returning the [`Cursor`] closest to it.
+    /// Ignores any transform set by [`Text::set_transform`].
+    ///
+    pub fn hit(&self, point: Vec2) -> Option<Cursor> {
+        let point = self.layout_point(point);
+
+        self.buffer.hit(point.x, point.y)
+    }
+
+    /// Returns the [`Attrs`] metadata of the glyph under a screen point.
+    /// Use it with [`Attrs::metadata`] to find which span got clicked, like a link.
+    /// Ignores any transform set by [`Text::set_transform`].
+    ///
+    pub fn metadata_at(&self, point: Vec2) -> Option<usize> {
+        let point = self.layout_point(point);
+
+        self.buffer
+            .layout_runs()
+            .find(|run| {
+                point.y >= run.line_top
+                    && point.y < run.line_top + run.line_height
+            })?
+            .glyphs
+            .iter()
+            .find(|glyph| point.x >= glyph.x && point.x < glyph.x + glyph.w)
+            .map(|glyph| glyph.metadata)
+    }
+
     /// Returns Visible Width and Line details of the Rendered [`Text`].
     pub fn visible_details(&self) -> VisibleDetails {
         let (width, lines) = self.buffer.layout_runs().fold(
@@ -581,6 +934,7 @@ impl Text {
 
     /// Allows measuring the String for how big it will be when Rendering.
     /// This will not create any buffers in the rendering system.
+    /// Use a [`TextMeasurer`] to measure many Strings with one [`Buffer`].
     ///
     pub fn measure_string(
         font_system: &mut FontSystem,
@@ -588,34 +942,250 @@ impl Text {
         attrs: Attrs,
         options: TextOptions,
     ) -> Vec2 {
-        let mut buffer = Buffer::new(
-            font_system,
-            options
-                .metrics
-                .unwrap_or(Metrics::new(16.0, 16.0).scale(options.scale)),
-        );
+        let metrics = options
+            .metrics
+            .unwrap_or(Metrics::new(16.0, 16.0).scale(options.scale));
 
-        buffer.set_wrap(font_system, options.wrap);
-        buffer.set_size(
+        TextMeasurer::new(font_system, metrics).measure_string(
             font_system,
-            options.buffer_width,
-            options.buffer_height,
+            text,
+            attrs,
+            options,
+        )
+    }
+}
+
+/// Converts a swash color glyph into the straight alpha RGBA layout the
+/// emoji [`crate::AtlasSet`] expects. Layered color outlines get blended by
+/// swash into premultiplied RGBA so they are unpremultiplied here. Raw 32
+/// bit color bitmaps are copied by swash as stored, which is premultiplied
+/// BGRA, so they also get swizzled when bgra is true. Decoded PNG bitmaps
+/// are already straight RGBA.
+///
+fn color_glyph_rgba(
+    image: &cosmic_text::SwashImage,
+    bgra: bool,
+) -> Cow<'_, [u8]> {
+    let bgra = bgra && matches!(image.source, Source::ColorBitmap(_));
+
+    if !bgra && !matches!(image.source, Source::ColorOutline(_)) {
+        return Cow::Borrowed(&image.data);
+    }
+
+    let mut data = image.data.clone();
+
+    for pixel in data.chunks_exact_mut(4) {
+        if bgra {
+            pixel.swap(0, 2);
+        }
+
+        let alpha = pixel[3] as u32;
+
+        if alpha > 0 && alpha < 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u32 * 255 + alpha / 2) / alpha)
+                    .min(255) as u8;
+            }
+        }
+    }
+
+    Cow::Owned(data)
+}
+
+/// Returns true if swash rendered the color bitmap glyph from a raw 32 bit
+/// CBDT strike instead of a PNG one.
+///
+fn raw_color_strike(
+    font_system: &mut FontSystem,
+    image: &cosmic_text::SwashImage,
+    cache_key: cosmic_text::CacheKey,
+) -> bool {
+    if !matches!(image.source, Source::ColorBitmap(_)) {
+        return false;
+    }
+
+    font_system.get_font(cache_key.font_id).is_some_and(|font| {
+        raw_strike_format(
+            font.as_swash(),
+            cache_key.glyph_id,
+            f32::from_bits(cache_key.font_size_bits),
+        )
+    })
+}
+
+/// Finds the color strike swash picks for a glyph at a size and returns
+/// true if its CBLC index stores the glyph as a raw 32 bit bitmap. sbix
+/// strikes only hold PNGs so they never match.
+///
+fn raw_strike_format(font: FontRef, glyph_id: u16, size: f32) -> bool {
+    const CBLC: swash::Tag = swash::tag_from_bytes(b"CBLC");
+    const SBIX: swash::Tag = swash::tag_from_bytes(b"sbix");
+
+    if size == 0.0 || font.table(SBIX).is_some() {
+        return false;
+    }
+
+    let Some(strike) = font
+        .color_strikes()
+        .find_by_nearest_ppem(size as u16, glyph_id)
+    else {
+        return false;
+    };
+
+    if strike.bit_depth() != 32 {
+        return false;
+    }
+
+    let Some(cblc) = font.table(CBLC) else {
+        return false;
+    };
+
+    cblc_image_format(cblc, strike.ppem(), glyph_id)
+        // Formats 1 and 6 are byte aligned pixels. 17 to 19 are PNGs and the
+        // bit aligned formats get decoded by swash as masks.
+        .is_some_and(|format| matches!(format, 1 | 6))
+}
+
+/// Reads the image format of the CBLC index subtable holding a glyph in the
+/// 32 bit strike of the ppem.
+///
+fn cblc_image_format(cblc: &[u8], ppem: u16, glyph_id: u16) -> Option<u16> {
+    let read_u16 = |offset: usize| {
+        Some(u16::from_be_bytes(
+            cblc.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    };
+    let read_u32 = |offset: usize| {
+        Some(u32::from_be_bytes(
+            cblc.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    };
+
+    let num_sizes = read_u32(4)? as usize;
+
+    for size in 0..num_sizes {
+        let record = 8 + size * 48;
+        let (start, end) = (read_u16(record + 40)?, read_u16(record + 42)?);
+
+        if *cblc.get(record + 45)? as u16 != ppem
+            || *cblc.get(record + 46)? != 32
+            || glyph_id < start
+            || glyph_id > end
+        {
+            continue;
+        }
+
+        let array = read_u32(record)? as usize;
+        let count = read_u32(record + 8)? as usize;
+
+        for index in 0..count {
+            let entry = array + index * 8;
+
+            if (read_u16(entry)?..=read_u16(entry + 2)?).contains(&glyph_id) {
+                let header = array + read_u32(entry + 4)? as usize;
+                return read_u16(header + 2);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmic_text::SwashImage;
+    use swash::scale::StrikeWith;
+
+    /// Builds a font holding only a CBDT and CBLC table with one 16 ppem,
+    /// 32 bit strike that stores glyph 1 in the image format.
+    fn cbdt_font(image_format: u16) -> Vec<u8> {
+        let mut cblc = Vec::new();
+        cblc.extend(0x0003_0000u32.to_be_bytes());
+        cblc.extend(1u32.to_be_bytes());
+        // BitmapSize record pointing at the IndexSubTableArray after it.
+        cblc.extend(56u32.to_be_bytes());
+        cblc.extend(24u32.to_be_bytes());
+        cblc.extend(1u32.to_be_bytes());
+        cblc.extend(0u32.to_be_bytes());
+        cblc.extend([0; 24]);
+        cblc.extend(1u16.to_be_bytes());
+        cblc.extend(1u16.to_be_bytes());
+        cblc.extend([16, 16, 32, 1]);
+        // IndexSubTableArray with one range for glyph 1.
+        cblc.extend(1u16.to_be_bytes());
+        cblc.extend(1u16.to_be_bytes());
+        cblc.extend(8u32.to_be_bytes());
+        // IndexSubTable format 1 header and its glyph offsets.
+        cblc.extend(1u16.to_be_bytes());
+        cblc.extend(image_format.to_be_bytes());
+        cblc.extend(4u32.to_be_bytes());
+        cblc.extend(0u32.to_be_bytes());
+        cblc.extend(8u32.to_be_bytes());
+
+        let cbdt = 0x0003_0000u32.to_be_bytes();
+        let mut font = Vec::new();
+        font.extend(0x0001_0000u32.to_be_bytes());
+        font.extend(2u16.to_be_bytes());
+        font.extend([0, 32, 0, 1, 0, 0]);
+
+        let mut offset = 12 + 2 * 16;
+        for (tag, table) in [(b"CBDT", &cbdt[..]), (b"CBLC", &cblc[..])] {
+            font.extend(tag);
+            font.extend(0u32.to_be_bytes());
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((table.len() as u32).to_be_bytes());
+            offset += table.len();
+        }
+
+        font.extend(cbdt);
+        font.extend(cblc);
+        font
+    }
+
+    fn color_image(source: Source, data: Vec<u8>) -> SwashImage {
+        let mut image = SwashImage::new();
+        image.source = source;
+        image.content = SwashContent::Color;
+        image.data = data;
+        image
+    }
+
+    #[test]
+    fn raw_strikes_are_told_apart_from_png_strikes() {
+        let raw = cbdt_font(1);
+        let png = cbdt_font(17);
+        let raw = FontRef::from_index(&raw, 0).unwrap();
+        let png = FontRef::from_index(&png, 0).unwrap();
+
+        assert!(raw_strike_format(raw, 1, 16.0));
+        assert!(raw_strike_format(raw, 1, 32.0));
+        assert!(!raw_strike_format(raw, 2, 16.0));
+        assert!(!raw_strike_format(png, 1, 16.0));
+    }
+
+    #[test]
+    fn raw_strike_pixels_become_straight_rgba() {
+        // Premultiplied BGRA of half transparent red and opaque blue.
+        let image = color_image(
+            Source::ColorBitmap(StrikeWith::BestFit),
+            vec![0, 0, 128, 128, 255, 0, 0, 255],
         );
-        buffer.set_text(font_system, text, attrs, options.shaping);
 
-        let (width, total_lines) = buffer.layout_runs().fold(
-            (0.0, 0usize),
-            |(width, total_lines), run| {
-                (run.line_w.max(width), total_lines + 1)
-            },
+        assert_eq!(
+            &color_glyph_rgba(&image, true)[..],
+            &[255, 0, 0, 128, 0, 0, 255, 255]
         );
+        assert_eq!(
+            &color_glyph_rgba(&image, false)[..],
+            &[0, 0, 128, 128, 255, 0, 0, 255]
+        );
+    }
 
-        let (max_width, max_height) = buffer.size();
-        let height = total_lines as f32 * buffer.metrics().line_height;
+    #[test]
+    fn color_outline_pixels_are_unpremultiplied() {
+        let image = color_image(Source::ColorOutline(0), vec![128, 0, 64, 128]);
 
-        Vec2::new(
-            width.min(max_width.unwrap_or(0.0).max(width)),
-            height.min(max_height.unwrap_or(0.0).max(height)),
-        )
+        assert_eq!(&color_glyph_rgba(&image, true)[..], &[255, 0, 128, 128]);
     }
 }
@@ -13,6 +13,17 @@ pub struct TextVertex {
     pub color: u32,
     pub camera_type: u32,
     pub is_color: u32,
+    /// Index of the text [`crate::AtlasSet`] within [`crate::TextAtlas`].
+    pub atlas: u32,
+    /// Column major 2x2 transform applied around the pivot.
+    pub transform: [f32; 4],
+    /// Screen position the transform is applied around.
+    pub pivot: [f32; 2],
+}
+
+impl TextVertex {
+    /// is_color value used for solid background quads that skip the atlas.
+    pub const BACKGROUND: u32 = 2;
 }
 
 impl Default for TextVertex {
@@ -25,13 +36,16 @@ impl Default for TextVertex {
             color: 0,
             camera_type: 0,
             is_color: 0,
+            atlas: 0,
+            transform: [1.0, 0.0, 0.0, 1.0],
+            pivot: [0.0; 2],
         }
     }
 }
 
 impl BufferLayout for TextVertex {
     fn attributes() -> Vec<wgpu::VertexAttribute> {
-        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32x2, 3 => Float32x2, 4 => Uint32, 5 => Uint32, 6 => Uint32, 7 => Uint32]
+        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32x2, 3 => Float32x2, 4 => Uint32, 5 => Uint32, 6 => Uint32, 7 => Uint32, 8 => Uint32, 9 => Float32x4, 10 => Float32x2]
             .to_vec()
     }
 
@@ -54,6 +68,6 @@ impl BufferLayout for TextVertex {
     }
 
     fn stride() -> usize {
-        std::mem::size_of::<[f32; 11]>()
+        std::mem::size_of::<[f32; 18]>()
     }
 }
@@ -7,10 +7,14 @@ pub use render::*;
 pub use vertex::*;
 
 use crate::{
-    AtlasSet, Bounds, CameraType, Color, DrawOrder, FlipStyle, GpuRenderer,
-    Index, OrderedIndex, Vec2, Vec3, Vec4,
+    apply_opacity, AtlasSet, Bounds, CameraType, Color, DrawOrder, FlipStyle,
+    GpuRenderer, Index, OrderedIndex, TextureId, Vec2, Vec3, Vec4,
 };
 
+/// Reserved [`AtlasSet`] key of the white texture used by
+/// [`Image::set_solid_color`].
+pub const WHITE_PIXEL_KEY: &str = "ascending_graphics::white_pixel";
+
 /// Basic and Fast Image Rendering Type. Best used for Sprites and Objects in the world.
 pub struct Image {
     /// Position of the object
@@ -31,7 +35,7 @@ pub struct Image {
     /// Global Camera the Shader will use to render the object with
     pub camera_type: CameraType,
     /// Texture area location in Atlas.
-    pub texture: Option<usize>,
+    pub texture: Option<TextureId>,
     /// Buffer's store Index.
     pub store_id: Index,
     /// Ordering Type, used to order the Stores in the buffers.
@@ -46,6 +50,16 @@ pub struct Image {
     pub rotation_angle: f32,
     /// Overides the absolute order values based on position.
     pub order_override: Option<Vec3>,
+    /// Explicit key to sort by instead of the position.
+    pub sort_key: Option<u64>,
+    /// Renders a solid color quad when there is no texture.
+    pub solid_color: bool,
+    /// Opacity from 0.0 to 1.0 multiplied into the color's alpha when
+    /// rendering. Default is 1.0.
+    pub opacity: f32,
+    /// [`crate::ImagePipelineVariant`] id to render with instead of the
+    /// buffer layer's. Used when added with [`crate::ImageRenderer::image_update`].
+    pub pipeline_variant: Option<u32>,
     /// When true tells system to update the buffers.
     pub changed: bool,
 }
@@ -54,7 +68,7 @@ impl Image {
     /// Creates a new [`Image`] with rendering layer.
     ///
     pub fn new(
-        texture: Option<usize>,
+        texture: Option<TextureId>,
         renderer: &mut GpuRenderer,
         render_layer: u32,
     ) -> Self {
@@ -76,8 +90,12 @@ impl Image {
             render_layer,
             bounds: None,
             order_override: None,
+            sort_key: None,
             flip_style: FlipStyle::None,
             rotation_angle: 0.0,
+            solid_color: false,
+            opacity: 1.0,
+            pipeline_variant: None,
             changed: true,
         }
     }
@@ -88,6 +106,15 @@ impl Image {
         renderer.remove_buffer(self.store_id);
     }
 
+    /// Sets an explicit key the [`Image`] sorts by instead of its position.
+    /// Keyed objects draw after unkeyed ones on the same order layer.
+    ///
+    pub fn set_sort_key(&mut self, sort_key: Option<u64>) -> &mut Self {
+        self.changed = true;
+        self.sort_key = sort_key;
+        self
+    }
+
     /// Updates the [`Image`]'s order_override.
     ///
     pub fn set_order_override(
@@ -172,7 +199,7 @@ impl Image {
 
     /// Updates the [`Image`]'s Texture ID.
     ///
-    pub fn set_texture(&mut self, texture: Option<usize>) -> &mut Self {
+    pub fn set_texture(&mut self, texture: Option<TextureId>) -> &mut Self {
         self.changed = true;
         self.texture = texture;
         self
@@ -186,6 +213,34 @@ impl Image {
         self
     }
 
+    /// Updates the [`Image`]'s opacity. This fades the [`Image`] without
+    /// changing its [`Color`].
+    ///
+    pub fn set_opacity(&mut self, opacity: f32) -> &mut Self {
+        self.changed = true;
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the [`crate::ImagePipelineVariant`] the [`Image`] renders with
+    /// instead of the buffer layer's. None uses the buffer layer's.
+    ///
+    pub fn set_pipeline_variant(&mut self, variant: Option<u32>) -> &mut Self {
+        self.pipeline_variant = variant;
+        self
+    }
+
+    /// Renders the [`Image`] as a solid [`Color`] quad while it has no
+    /// texture by sampling a white texture uploaded once into the
+    /// [`AtlasSet`] under [`WHITE_PIXEL_KEY`].
+    ///
+    pub fn set_solid_color(&mut self, color: Color) -> &mut Self {
+        self.changed = true;
+        self.solid_color = true;
+        self.color = color;
+        self
+    }
+
     /// Updates the [`Image`]'s [`CameraType`].
     ///
     pub fn set_camera_type(&mut self, camera_type: CameraType) -> &mut Self {
@@ -209,6 +264,7 @@ impl Image {
         renderer: &mut GpuRenderer,
         atlas: &mut AtlasSet,
     ) {
+        let solid = self.texture.is_none() && self.solid_color;
         let allocation = match &self.texture {
             Some(id) => {
                 if let Some(allocation) = atlas.get(*id) {
@@ -217,29 +273,42 @@ impl Image {
                     return;
                 }
             }
+            None if self.solid_color => {
+                match Self::white_pixel(renderer, atlas) {
+                    Some(allocation) => allocation,
+                    None => return,
+                }
+            }
             None => return,
         };
 
         let (u, v, width, height) = allocation.rect();
-        let tex_data = (
-            self.uv.x + u as f32,
-            self.uv.y + v as f32,
-            self.uv.z.min(width as f32),
-            self.uv.w.min(height as f32),
-        );
+        let tex_data = if solid {
+            // Sample the center texel so filtering only blends white.
+            (u as f32 + 1.0, v as f32 + 1.0, 1.0, 1.0)
+        } else {
+            (
+                self.uv.x + u as f32,
+                self.uv.y + v as f32,
+                self.uv.z.min(width as f32),
+                self.uv.w.min(height as f32),
+            )
+        };
 
+        let color = apply_opacity(self.color, self.opacity);
         let instance = ImageVertex {
             position: self.pos.to_array(),
             hw: self.hw.to_array(),
             tex_data: tex_data.into(),
-            color: self.color.0,
+            color: color.0,
             frames: self.frames.to_array(),
-            animate: u32::from(self.animate),
+            animate: u32::from(self.animate && !solid),
             camera_type: self.camera_type as u32,
             time: self.switch_time,
             layer: allocation.layer as i32,
             flip_style: self.flip_style as u32,
             angle: self.rotation_angle,
+            alpha_mode: atlas.shader_alpha_mode(),
         };
 
         if let Some(store) = renderer.get_buffer_mut(self.store_id) {
@@ -255,10 +324,36 @@ impl Image {
         };
 
         self.order =
-            DrawOrder::new(self.color.a() < 255, &order_pos, self.render_layer);
+            DrawOrder::new(color.a() < 255, &order_pos, self.render_layer)
+                .with_sort_key(self.sort_key);
         self.changed = false;
     }
 
+    /// Gets the white texture from the [`AtlasSet`] uploading it if needed.
+    ///
+    fn white_pixel(
+        renderer: &GpuRenderer,
+        atlas: &mut AtlasSet,
+    ) -> Option<crate::Allocation> {
+        let id = match atlas.lookup(&WHITE_PIXEL_KEY.to_string()) {
+            Some(id) => id,
+            None => {
+                let bytes = vec![255u8; 9 * atlas.bytes_per_pixel() as usize];
+
+                atlas.upload(
+                    WHITE_PIXEL_KEY.to_string(),
+                    &bytes,
+                    3,
+                    3,
+                    0,
+                    renderer,
+                )?
+            }
+        };
+
+        atlas.get(id)
+    }
+
     /// Used to check and update the vertex array.
     /// Returns a [`OrderedIndex`] used in Rendering.
     ///
@@ -1,12 +1,20 @@
 use crate::{
-    AtlasSet, GpuRenderer, GraphicsError, Image, ImageRenderPipeline,
-    ImageVertex, InstanceBuffer, OrderedIndex, StaticVertexBuffer, System,
+    AHashMap, AtlasSet, GpuRenderer, GraphicsError, Image,
+    ImagePipelineVariant, ImageRenderPipeline, ImageVertex, InstanceBuffer,
+    OrderedIndex, StaticVertexBuffer, System, VariantRuns,
 };
+use log::warn;
 
 /// Instance Buffer Setup for [`Image`].
 ///
 pub struct ImageRenderer {
     pub buffer: InstanceBuffer<ImageVertex>,
+    /// Custom [`ImagePipelineVariant`] ids used per buffer layer.
+    pub(crate) pipeline_variants: AHashMap<usize, u32>,
+    /// [`AtlasSet`] id used by each buffer layer since the last finalize.
+    pub(crate) layer_atlases: AHashMap<usize, u64>,
+    /// Per [`Image`] variants and the instance ranges that share them.
+    pub(crate) variant_runs: VariantRuns,
 }
 
 impl ImageRenderer {
@@ -15,6 +23,9 @@ impl ImageRenderer {
     pub fn new(renderer: &GpuRenderer) -> Result<Self, GraphicsError> {
         Ok(Self {
             buffer: InstanceBuffer::new(renderer.gpu_device(), 512),
+            pipeline_variants: AHashMap::default(),
+            layer_atlases: AHashMap::default(),
+            variant_runs: VariantRuns::default(),
         })
     }
 
@@ -38,6 +49,8 @@ impl ImageRenderer {
     /// Must be called after all the [`ImageRenderer::add_buffer_store`]'s.
     ///
     pub fn finalize(&mut self, renderer: &mut GpuRenderer) {
+        self.layer_atlases.clear();
+        self.variant_runs.build(renderer, &self.buffer);
         self.buffer.finalize(renderer)
     }
 
@@ -49,6 +62,11 @@ impl ImageRenderer {
     /// - atlas: [`AtlasSet`] the [`Image`] needs to render with.
     /// - buffer_layer: The Buffer Layer we want to add this Object too.
     ///
+    /// Each buffer layer is drawn with a single bound [`AtlasSet`], so every
+    /// [`Image`] in the same buffer layer must use the same [`AtlasSet`].
+    /// Mixing them logs a warning as they would render with the wrong texture.
+    /// The [`Image`]'s pipeline_variant is used instead of the buffer layer's.
+    ///
     pub fn image_update(
         &mut self,
         image: &mut Image,
@@ -57,7 +75,18 @@ impl ImageRenderer {
         buffer_layer: usize,
     ) {
         let index = image.update(renderer, atlas);
+        let atlas_id =
+            *self.layer_atlases.entry(buffer_layer).or_insert(atlas.id());
+
+        if atlas_id != atlas.id() {
+            warn!(
+                "Image buffer layer {} mixes AtlasSets. Only one AtlasSet can be bound per buffer layer.",
+                buffer_layer
+            );
+        }
 
+        self.variant_runs
+            .insert(index.index, image.pipeline_variant);
         self.add_buffer_store(renderer, index, buffer_layer);
     }
 
@@ -67,6 +96,54 @@ impl ImageRenderer {
     pub fn use_clipping(&mut self) {
         self.buffer.set_as_clipped();
     }
+
+    /// Sets the [`ImagePipelineVariant`] used to render a buffer layer.
+    /// Every [`Image`] added to this buffer layer will render using the custom
+    /// fragment shader created by [`GpuRenderer::create_image_pipeline_variant`]
+    /// unless the [`Image`] sets its own with [`Image::set_pipeline_variant`].
+    /// None resets the buffer layer back to the default [`ImageRenderPipeline`].
+    ///
+    /// # Arguments
+    /// - buffer_layer: The Buffer Layer the variant will be used for.
+    /// - variant: The user defined variant id.
+    ///
+    pub fn set_pipeline_variant(
+        &mut self,
+        buffer_layer: usize,
+        variant: Option<u32>,
+    ) {
+        if let Some(variant) = variant {
+            self.pipeline_variants.insert(buffer_layer, variant);
+        } else {
+            self.pipeline_variants.remove(&buffer_layer);
+        }
+    }
+
+    /// Returns the [`ImagePipelineVariant`] id used by the buffer layer if any.
+    ///
+    pub fn pipeline_variant(&self, buffer_layer: usize) -> Option<u32> {
+        self.pipeline_variants.get(&buffer_layer).copied()
+    }
+
+    /// Returns the [`wgpu::RenderPipeline`] the buffer layer will render with
+    /// or the [`Image`]'s own variant if it has one.
+    /// Falls back to [`ImageRenderPipeline`] if the variant was not created.
+    ///
+    pub(crate) fn get_pipeline<'p>(
+        &self,
+        renderer: &'p GpuRenderer,
+        buffer_layer: usize,
+        variant: Option<u32>,
+    ) -> &'p wgpu::RenderPipeline {
+        variant
+            .or(self.pipeline_variant(buffer_layer))
+            .and_then(|variant| {
+                renderer.get_pipelines(ImagePipelineVariant(variant))
+            })
+            .unwrap_or_else(|| {
+                renderer.get_pipelines(ImageRenderPipeline).unwrap()
+            })
+    }
 }
 
 /// Trait used to Grant Direct [`Image`] Rendering to [`wgpu::RenderPass`]
@@ -85,6 +162,17 @@ where
         system: &'b System<Controls>,
         buffer_layer: usize,
     );
+
+    /// Renders every buffer layer of [`Image`]'s that have been processed and finalized.
+    /// Respects clipped and unclipped buffers and each buffer layers pipeline variant.
+    ///
+    fn render_all_images(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b ImageRenderer,
+        atlas: &'b AtlasSet,
+        system: &'b System<Controls>,
+    );
 }
 
 impl<'a, 'b, Controls> RenderImage<'a, 'b, Controls> for wgpu::RenderPass<'a>
@@ -109,10 +197,17 @@ where
                 if buffer.buffer.count() > 0 {
                     self.set_bind_group(1, atlas.bind_group(), &[]);
                     self.set_vertex_buffer(1, buffer.buffer.instances(None));
-                    self.set_pipeline(
-                        renderer.get_pipelines(ImageRenderPipeline).unwrap(),
-                    );
                     for (details, bounds, camera_type) in details {
+                        self.set_pipeline(
+                            buffer.get_pipeline(
+                                renderer,
+                                buffer_layer,
+                                buffer
+                                    .variant_runs
+                                    .variant_at(buffer_layer, details.start),
+                            ),
+                        );
+
                         if let Some(bounds) = bounds {
                             let bounds =
                                 system.world_to_screen(*camera_type, bounds);
@@ -150,16 +245,50 @@ where
             if buffer.buffer.count() > 0 {
                 self.set_bind_group(1, atlas.bind_group(), &[]);
                 self.set_vertex_buffer(1, buffer.buffer.instances(None));
-                self.set_pipeline(
-                    renderer.get_pipelines(ImageRenderPipeline).unwrap(),
-                );
-
-                self.draw_indexed(
-                    0..StaticVertexBuffer::index_count(),
-                    0,
-                    details.start..details.end,
-                );
+                if let Some(runs) = buffer.variant_runs.layer(buffer_layer) {
+                    for (range, variant) in runs {
+                        self.set_pipeline(buffer.get_pipeline(
+                            renderer,
+                            buffer_layer,
+                            *variant,
+                        ));
+                        self.draw_indexed(
+                            0..StaticVertexBuffer::index_count(),
+                            0,
+                            range.clone(),
+                        );
+                    }
+                } else {
+                    self.set_pipeline(buffer.get_pipeline(
+                        renderer,
+                        buffer_layer,
+                        None,
+                    ));
+                    self.draw_indexed(
+                        0..StaticVertexBuffer::index_count(),
+                        0,
+                        details.start..details.end,
+                    );
+                }
             }
         }
     }
+
+    fn render_all_images(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b ImageRenderer,
+        atlas: &'b AtlasSet,
+        system: &'b System<Controls>,
+    ) {
+        let layers = if buffer.buffer.is_clipped() {
+            buffer.buffer.clipped_buffers.len()
+        } else {
+            buffer.buffer.buffers.len()
+        };
+
+        for buffer_layer in 0..layers {
+            self.render_image(renderer, buffer, atlas, system, buffer_layer);
+        }
+    }
 }
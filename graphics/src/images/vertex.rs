@@ -17,6 +17,7 @@ pub struct ImageVertex {
     pub layer: i32,
     pub angle: f32,
     pub flip_style: u32,
+    pub alpha_mode: u32,
 }
 
 impl Default for ImageVertex {
@@ -33,13 +34,14 @@ impl Default for ImageVertex {
             layer: 0,
             angle: 0.0,
             flip_style: 0,
+            alpha_mode: 0,
         }
     }
 }
 
 impl BufferLayout for ImageVertex {
     fn attributes() -> Vec<wgpu::VertexAttribute> {
-        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32x2, 3 => Float32x4, 4 => Uint32, 5 => Float32x2, 6 => Uint32, 7 => Uint32, 8 => Uint32, 9 => Sint32, 10 => Float32, 11 => Uint32 ]
+        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32x2, 3 => Float32x4, 4 => Uint32, 5 => Float32x2, 6 => Uint32, 7 => Uint32, 8 => Uint32, 9 => Sint32, 10 => Float32, 11 => Uint32, 12 => Uint32 ]
             .to_vec()
     }
 
@@ -63,6 +65,6 @@ impl BufferLayout for ImageVertex {
     }
 
     fn stride() -> usize {
-        std::mem::size_of::<[f32; 18]>()
+        std::mem::size_of::<[f32; 19]>()
     }
 }
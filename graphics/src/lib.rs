@@ -15,7 +15,7 @@ pub use atlas_set::*;
 pub use cosmic_text::Color;
 pub use error::*;
 pub use font::*;
-pub use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
+pub use glam::{Mat2, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
 pub use images::*;
 pub use lights::*;
 pub use maps::*;
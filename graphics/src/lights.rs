@@ -1,3 +1,4 @@
+mod falloff;
 mod pipeline;
 mod render;
 mod uniforms;
@@ -9,8 +10,8 @@ pub use uniforms::*;
 pub use vertex::*;
 
 use crate::{
-    CameraType, Color, DrawOrder, GpuRenderer, Index, OrderedIndex, Vec2, Vec3,
-    Vec4,
+    AHashSet, CameraType, Color, DrawOrder, GpuRenderer, Index, OrderedIndex,
+    Vec2, Vec3, Vec4,
 };
 use slotmap::SlotMap;
 use std::mem;
@@ -51,8 +52,11 @@ pub struct DirectionalLight {
     pub pos: Vec2,
     pub color: Color,
     pub max_distance: f32,
+    /// Width of the beam in degrees.
     pub max_width: f32,
     pub anim_speed: f32,
+    /// Direction of the beam in degrees. 0 points along +X and it turns
+    /// towards +Y as it increases so 90 points along +Y.
     pub angle: f32,
     pub dither: f32,
     pub fade_distance: f32,
@@ -62,6 +66,48 @@ pub struct DirectionalLight {
 }
 
 impl DirectionalLight {
+    /// Sets the beam direction in degrees. 0 points along +X.
+    ///
+    pub fn set_angle_degrees(&mut self, degrees: f32) -> &mut Self {
+        self.angle = degrees;
+        self
+    }
+
+    /// Returns the beam direction in degrees. 0 points along +X.
+    ///
+    pub fn angle_degrees(&self) -> f32 {
+        self.angle
+    }
+
+    /// Sets the beam direction in radians. 0 points along +X.
+    ///
+    pub fn set_angle_radians(&mut self, radians: f32) -> &mut Self {
+        self.angle = radians.to_degrees();
+        self
+    }
+
+    /// Returns the beam direction in radians. 0 points along +X.
+    ///
+    pub fn angle_radians(&self) -> f32 {
+        self.angle.to_radians()
+    }
+
+    /// Aims the beam along a direction. A zero direction is ignored.
+    ///
+    pub fn set_direction(&mut self, direction: Vec2) -> &mut Self {
+        if direction != Vec2::ZERO {
+            self.angle = direction.y.atan2(direction.x).to_degrees();
+        }
+
+        self
+    }
+
+    /// Returns the normalized direction the beam points along.
+    ///
+    pub fn direction(&self) -> Vec2 {
+        Vec2::from_angle(self.angle.to_radians())
+    }
+
     fn to_raw(&self) -> DirectionalLightRaw {
         DirectionalLightRaw {
             pos: self.pos.to_array(),
@@ -80,8 +126,14 @@ impl DirectionalLight {
 }
 
 /// Rendering data for world Light and all Lights.
+///
+/// All of the [`AreaLight`]'s and [`DirectionalLight`]'s within a [`Lights`]
+/// are drawn in one pass at [`Lights::z`]. To light world objects at
+/// different depths use a [`Lights`] and [`crate::LightRenderer`] per depth
+/// and render each one between the world layers it should sit between.
 pub struct Lights {
-    /// Z Position of the Main Light Layer.
+    /// Z Position of the Main Light Layer. Used for depth testing and in the
+    /// [`DrawOrder`] so the lights sort against other objects.
     pub z: f32,
     /// Color of the main light layer.
     pub world_color: Vec4,
@@ -107,6 +159,10 @@ pub struct Lights {
     pub directionals_changed: bool,
     /// If any [`AreaLight`] got updated we need to update the buffers too.
     pub areas_changed: bool,
+    /// [`AreaLight`]'s that are hidden and not uploaded for rendering.
+    pub hidden_area_lights: AHashSet<Index>,
+    /// [`DirectionalLight`]'s that are hidden and not uploaded for rendering.
+    pub hidden_directional_lights: AHashSet<Index>,
 }
 
 impl Lights {
@@ -130,6 +186,8 @@ impl Lights {
             changed: true,
             directionals_changed: true,
             areas_changed: true,
+            hidden_area_lights: AHashSet::default(),
+            hidden_directional_lights: AHashSet::default(),
         }
     }
 
@@ -143,8 +201,11 @@ impl Lights {
         let instance = LightsVertex {
             world_color: self.world_color.to_array(),
             enable_lights: u32::from(self.enable_lights),
-            dir_count: self.directional_lights.len() as u32,
-            area_count: self.area_lights.len() as u32,
+            dir_count: (self.directional_lights.len()
+                - self.hidden_directional_lights.len())
+                as u32,
+            area_count: (self.area_lights.len() - self.hidden_area_lights.len())
+                as u32,
             z: self.z,
         };
 
@@ -157,12 +218,23 @@ impl Lights {
 
         self.order = DrawOrder::new(
             self.world_color.w < 1.0,
-            &Vec3::default(),
+            &Vec3::new(0.0, 0.0, self.z),
             self.render_layer,
         );
         self.changed = false;
     }
 
+    /// Sets the Z Position the [`Lights`] are drawn at.
+    ///
+    /// # Arguments
+    /// - z: Depth to draw the lights at.
+    ///
+    pub fn set_z(&mut self, z: f32) -> &mut Self {
+        self.z = z;
+        self.changed = true;
+        self
+    }
+
     /// Inserts a [`AreaLight`] into [`Lights`].
     /// Returns the [`AreaLight`]'s [`Index`].
     ///
@@ -182,6 +254,7 @@ impl Lights {
         self.areas_changed = true;
         self.changed = true;
         self.area_lights.remove(key);
+        self.hidden_area_lights.remove(&key);
     }
 
     /// Gets a Optional mutable reference of a [`Index`]ed [`AreaLight`].
@@ -191,6 +264,76 @@ impl Lights {
         self.area_lights.get_mut(key)
     }
 
+    /// Shows or hides many [`AreaLight`]'s at once.
+    /// The lights buffer is only flagged for reupload once.
+    ///
+    /// # Arguments
+    /// - keys: [`Index`]'s of the [`AreaLight`]'s to change.
+    /// - visible: If the [`AreaLight`]'s should be rendered.
+    ///
+    pub fn set_area_lights_visible(&mut self, keys: &[Index], visible: bool) {
+        for key in keys {
+            if visible {
+                self.hidden_area_lights.remove(key);
+            } else if self.area_lights.contains_key(*key) {
+                self.hidden_area_lights.insert(*key);
+            }
+        }
+
+        self.areas_changed = true;
+        self.changed = true;
+    }
+
+    /// Shows or hides all [`AreaLight`]'s at once.
+    ///
+    pub fn set_all_area_lights_visible(&mut self, visible: bool) {
+        if visible {
+            self.hidden_area_lights.clear();
+        } else {
+            self.hidden_area_lights.extend(self.area_lights.keys());
+        }
+
+        self.areas_changed = true;
+        self.changed = true;
+    }
+
+    /// Returns if the [`AreaLight`] is visible.
+    ///
+    pub fn is_area_light_visible(&self, key: Index) -> bool {
+        self.area_lights.contains_key(key)
+            && !self.hidden_area_lights.contains(&key)
+    }
+
+    /// Returns the accumulated light level from 0.0 to 1.0 at a position
+    /// from all visible [`AreaLight`]'s and [`DirectionalLight`]'s.
+    /// Uses the same falloff as the light shader but ignores animation
+    /// and camera transforms, so `pos` must be in the lights coordinates.
+    ///
+    /// # Arguments
+    /// - pos: Position to test against the lights.
+    ///
+    pub fn light_level_at(&self, pos: Vec2) -> f32 {
+        let areas = self
+            .area_lights
+            .iter()
+            .filter(|(key, _)| !self.hidden_area_lights.contains(key))
+            .take(MAX_AREA_LIGHTS)
+            .map(|(_, light)| falloff::area_light_value(light, pos));
+        let dirs = self
+            .directional_lights
+            .iter()
+            .filter(|(key, _)| !self.hidden_directional_lights.contains(key))
+            .take(MAX_DIR_LIGHTS)
+            .map(|(_, light)| falloff::directional_light_value(light, pos));
+
+        areas
+            .chain(dirs)
+            .fold(0.0, |level: f32, value| {
+                level + (1.0 - level) * value.clamp(0.0, 1.0)
+            })
+            .clamp(0.0, 1.0)
+    }
+
     /// Inserts a [`DirectionalLight`] into [`Lights`].
     /// Returns the [`DirectionalLight`]'s [`Index`].
     ///
@@ -213,6 +356,7 @@ impl Lights {
         self.directionals_changed = true;
         self.changed = true;
         self.directional_lights.remove(key);
+        self.hidden_directional_lights.remove(&key);
     }
 
     /// Gets a Optional mutable reference of a [`Index`]ed [`DirectionalLight`].
@@ -225,6 +369,51 @@ impl Lights {
         self.directional_lights.get_mut(key)
     }
 
+    /// Shows or hides many [`DirectionalLight`]'s at once.
+    /// The lights buffer is only flagged for reupload once.
+    ///
+    /// # Arguments
+    /// - keys: [`Index`]'s of the [`DirectionalLight`]'s to change.
+    /// - visible: If the [`DirectionalLight`]'s should be rendered.
+    ///
+    pub fn set_directional_lights_visible(
+        &mut self,
+        keys: &[Index],
+        visible: bool,
+    ) {
+        for key in keys {
+            if visible {
+                self.hidden_directional_lights.remove(key);
+            } else if self.directional_lights.contains_key(*key) {
+                self.hidden_directional_lights.insert(*key);
+            }
+        }
+
+        self.directionals_changed = true;
+        self.changed = true;
+    }
+
+    /// Shows or hides all [`DirectionalLight`]'s at once.
+    ///
+    pub fn set_all_directional_lights_visible(&mut self, visible: bool) {
+        if visible {
+            self.hidden_directional_lights.clear();
+        } else {
+            self.hidden_directional_lights
+                .extend(self.directional_lights.keys());
+        }
+
+        self.directionals_changed = true;
+        self.changed = true;
+    }
+
+    /// Returns if the [`DirectionalLight`] is visible.
+    ///
+    pub fn is_directional_light_visible(&self, key: Index) -> bool {
+        self.directional_lights.contains_key(key)
+            && !self.hidden_directional_lights.contains(&key)
+    }
+
     /// Used to check and update the vertex array.
     /// Returns a [`OrderedIndex`] used in Rendering.
     ///
@@ -242,7 +431,12 @@ impl Lights {
         if self.areas_changed {
             let area_alignment: usize =
                 align_to(mem::size_of::<AreaLightRaw>(), 32) as usize;
-            for (i, (_key, light)) in self.area_lights.iter().enumerate() {
+            let visible = self
+                .area_lights
+                .iter()
+                .filter(|(key, _)| !self.hidden_area_lights.contains(key));
+
+            for (i, (_key, light)) in visible.enumerate() {
                 renderer.queue().write_buffer(
                     areas,
                     (i * area_alignment) as wgpu::BufferAddress,
@@ -256,7 +450,11 @@ impl Lights {
         if self.directionals_changed {
             let dir_alignment: usize =
                 align_to(mem::size_of::<DirectionalLightRaw>(), 48) as usize;
-            for (i, (_key, dir)) in self.directional_lights.iter().enumerate() {
+            let visible = self.directional_lights.iter().filter(|(key, _)| {
+                !self.hidden_directional_lights.contains(key)
+            });
+
+            for (i, (_key, dir)) in visible.enumerate() {
                 renderer.queue().write_buffer(
                     dirs,
                     (i * dir_alignment) as wgpu::BufferAddress,
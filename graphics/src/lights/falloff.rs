@@ -0,0 +1,87 @@
+//! CPU versions of the light falloff used by `lightshader.wgsl`.
+//! Any change to the shader math must also be made here so
+//! [`crate::Lights::light_level_at`] matches what is rendered.
+
+use crate::{AreaLight, DirectionalLight, Vec2};
+
+/// Smooth step between x0 and x1 centered on c with a width of w.
+pub(crate) fn fade(d: f32, x0: f32, x1: f32, c: f32, w: f32) -> f32 {
+    let w1 = w.max(0.000001);
+    let s_d = 1.0 / (1.0 + (-(c - d) / w1).exp());
+    x1 - (x0 + (x1 - x0) * (1.0 - s_d))
+}
+
+/// Matches the WGSL `%` which keeps the sign of the angle.
+fn normalize_360(angle: f32) -> f32 {
+    angle % 360.0
+}
+
+fn normalize_180(angle: f32) -> f32 {
+    let angle2 = normalize_360(angle);
+    let angle3 = if angle2 > 180.0 {
+        angle2 - 360.0
+    } else {
+        angle2
+    };
+
+    if angle2 < -180.0 {
+        angle2 + 360.0
+    } else {
+        angle3
+    }
+}
+
+fn within_range(test_angle: f32, a: f32, b: f32) -> bool {
+    let a2 = normalize_180(a - test_angle);
+    let b2 = normalize_180(b - test_angle);
+
+    a2 * b2 < 0.0 && (a2 - b2).abs() < 180.0
+}
+
+/// Returns how much a [`AreaLight`] lights `pos` from 0.0 to 1.0.
+///
+pub(crate) fn area_light_value(light: &AreaLight, pos: Vec2) -> f32 {
+    let cutoff = light.max_distance.max(0.1);
+    let dist = light.pos.distance(pos);
+
+    fade(dist, 0.0, 1.0, cutoff, light.dither)
+}
+
+/// Returns how much a [`DirectionalLight`] lights `pos` from 0.0 to 1.0.
+///
+pub(crate) fn directional_light_value(
+    light: &DirectionalLight,
+    pos: Vec2,
+) -> f32 {
+    let range = light.max_distance.max(0.1);
+    let w_angle = light.max_width.max(0.1);
+    let dir = light.angle;
+    let edge_fade_percent = light.edge_fade_distance;
+    let edge_fade_dist = light.fade_distance;
+    let s_angle = dir - (w_angle / 2.0);
+    let e_angle = dir + (w_angle / 2.0);
+    let deg = normalize_360(
+        (pos.y - light.pos.y).atan2(pos.x - light.pos.x) * 180.0
+            / std::f32::consts::PI,
+    );
+    let d = light.pos.distance(pos);
+
+    if d > range {
+        return 0.0;
+    }
+
+    if within_range(deg, s_angle, e_angle) {
+        (1.0 - ((deg - dir).abs() / (w_angle + 4.0 / 2.0)).min(1.0)
+            - edge_fade_percent)
+            .max(0.0)
+            / (1.0 - edge_fade_percent)
+    } else if within_range(
+        deg,
+        s_angle + edge_fade_dist,
+        e_angle - edge_fade_dist,
+    ) {
+        fade(d, 0.0, 1.0, range - 2.0, light.dither)
+    } else {
+        0.0
+    }
+}
@@ -20,9 +20,10 @@ impl PipeLineLayout for LightRenderPipeline {
         let shader = gpu_device.device().create_shader_module(
             wgpu::ShaderModuleDescriptor {
                 label: Some("Shader"),
-                source: wgpu::ShaderSource::Wgsl(
-                    include_str!("../shaders/lightshader.wgsl").into(),
-                ),
+                source: wgpu::ShaderSource::Wgsl(crate::load_shader(
+                    "lightshader.wgsl",
+                    include_str!("../shaders/lightshader.wgsl"),
+                )),
             },
         );
 
@@ -75,7 +76,7 @@ impl PipeLineLayout for LightRenderPipeline {
                     conservative: false,
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth32Float,
+                    format: gpu_device.depth_format(),
                     depth_write_enabled: true,
                     depth_compare: wgpu::CompareFunction::LessEqual,
                     stencil: wgpu::StencilState::default(),
@@ -12,6 +12,10 @@ use wgpu::util::{align_to, DeviceExt};
 
 /// Instance Buffer Setup for [`Lights`].
 ///
+/// Holds one set of light uniforms, so each [`Lights`] that needs its own
+/// [`crate::AreaLight`]'s and [`crate::DirectionalLight`]'s should use its
+/// own [`LightRenderer`].
+///
 pub struct LightRenderer {
     /// Instance Buffer holding all Rendering information for [`Lights`].
     pub buffer: InstanceBuffer<LightsVertex>,
@@ -168,6 +172,15 @@ where
         buffer: &'b LightRenderer,
         buffer_layer: usize,
     );
+
+    /// Renders every buffer layer of [`Lights`]'s that have been processed and finalized.
+    /// Shared pipeline and bind state is only set once.
+    ///
+    fn render_all_lights(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b LightRenderer,
+    );
 }
 
 impl<'a, 'b> RenderLights<'a, 'b> for wgpu::RenderPass<'a>
@@ -197,4 +210,34 @@ where
             }
         }
     }
+
+    fn render_all_lights(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b LightRenderer,
+    ) {
+        if buffer.buffer.count() == 0 {
+            return;
+        }
+
+        let mut state_set = false;
+
+        for details in buffer.buffer.buffers.iter().flatten() {
+            if !state_set {
+                self.set_bind_group(1, &buffer.area_bind_group, &[]);
+                self.set_bind_group(2, &buffer.dir_bind_group, &[]);
+                self.set_vertex_buffer(1, buffer.buffer.instances(None));
+                self.set_pipeline(
+                    renderer.get_pipelines(LightRenderPipeline).unwrap(),
+                );
+                state_set = true;
+            }
+
+            self.draw_indexed(
+                0..StaticVertexBuffer::index_count(),
+                0,
+                details.start..details.end,
+            );
+        }
+    }
 }
@@ -1,16 +1,18 @@
+mod animation;
 mod pipeline;
 mod render;
 mod vertex;
 
+pub use animation::*;
 pub use pipeline::*;
 pub use render::*;
 pub use vertex::*;
 
-use std::iter;
+use std::{collections::VecDeque, iter};
 
 use crate::{
-    AtlasSet, CameraType, DrawOrder, GpuRenderer, Index, OrderedIndex, Vec2,
-    Vec3,
+    AHashMap, AtlasSet, CameraType, DrawOrder, GpuRenderer, Index,
+    OrderedIndex, TextureId, UVec3, Vec2, Vec3,
 };
 use cosmic_text::Color;
 
@@ -75,7 +77,8 @@ impl MapLayers {
 
 #[derive(Copy, Clone)]
 pub struct TileData {
-    ///tiles allocation ID within the texture.
+    ///tiles allocation ID within the texture. This is the [`crate::TextureId`]'s
+    ///index and 0 is used for no tile.
     pub id: usize,
     pub color: Color,
 }
@@ -116,6 +119,11 @@ pub struct Map {
     /// Used to deturmine if the map can be rendered or if its just a preload.
     pub can_render: bool,
     pub camera_type: CameraType,
+    /// Tile index to (animation id, current frame allocation id) for tiles
+    /// animated with a [`TileAnimation`] from [`MapRenderer`].
+    pub tile_animations: AHashMap<usize, (usize, usize)>,
+    /// Color multiplied into every tile of a layer. Default is white.
+    pub layer_tints: [Color; MapLayers::Count as usize],
     /// If the position or a tile gets changed.
     pub changed: bool,
 }
@@ -128,6 +136,14 @@ impl Map {
         renderer: &mut GpuRenderer,
         atlas: &mut AtlasSet,
     ) {
+        self.create_vertices(atlas);
+        self.write_stores(renderer);
+    }
+
+    /// Builds the lower and upper vertices and orders on the CPU only.
+    /// Does not touch the renderer so many [`Map`]'s can be built at once.
+    ///
+    pub(crate) fn create_vertices(&mut self, atlas: &AtlasSet) {
         let atlas_width = atlas.size().x / self.tilesize;
 
         self.lower_buffer.clear();
@@ -135,6 +151,7 @@ impl Map {
 
         for layer in MapLayers::LAYERS {
             let z = layer.indexed_layers();
+            let tint = self.layer_tints[layer as usize];
 
             if self.filled_tiles[layer as usize] == 0 {
                 continue;
@@ -142,14 +159,19 @@ impl Map {
 
             for x in 0..32 {
                 for y in 0..32 {
-                    let tile = &self.tiles
-                        [(x + (y * 32) + (layer as u32 * 1024)) as usize];
+                    let index = (x + (y * 32) + (layer as u32 * 1024)) as usize;
+                    let tile = &self.tiles[index];
 
                     if tile.id == 0 {
                         continue;
                     }
 
-                    if let Some((allocation, _)) = atlas.peek(tile.id) {
+                    let id = match self.tile_animations.get(&index) {
+                        Some((_, frame)) if *frame > 0 => *frame,
+                        _ => tile.id,
+                    };
+
+                    if let Some((allocation, _)) = atlas.peek(TextureId(id)) {
                         let (posx, posy) = allocation.position();
 
                         let map_vertex = MapVertex {
@@ -163,6 +185,7 @@ impl Map {
                                 + ((posy / self.tilesize) * atlas_width),
                             texture_layer: allocation.layer as u32,
                             color: tile.color.0,
+                            tint: tint.0,
                             camera_type: self.camera_type as u32,
                         };
 
@@ -176,6 +199,30 @@ impl Map {
             }
         }
 
+        // Faded layer tints sort the buffer as transparent so Objects behind
+        // it still show through.
+        let fringe = MapLayers::Fringe as usize;
+        let lower_alpha =
+            self.layer_tints[..fringe].iter().any(|tint| tint.a() < 255);
+        let upper_alpha =
+            self.layer_tints[fringe..].iter().any(|tint| tint.a() < 255);
+
+        self.orders[0] = DrawOrder::new(
+            lower_alpha,
+            &Vec3::new(self.pos.x, self.pos.y, 9.0),
+            0,
+        );
+
+        self.orders[1] = DrawOrder::new(
+            upper_alpha,
+            &Vec3::new(self.pos.x, self.pos.y, 5.0),
+            1,
+        );
+    }
+
+    /// Copies the vertices from [`Map::create_vertices`] into the Buffers.
+    ///
+    pub(crate) fn write_stores(&mut self, renderer: &mut GpuRenderer) {
         if let Some(store) = renderer.get_buffer_mut(self.stores[0]) {
             let bytes = bytemuck::cast_slice(&self.lower_buffer);
             store.store.resize_with(bytes.len(), || 0);
@@ -190,11 +237,6 @@ impl Map {
             store.changed = true;
         }
 
-        self.orders[0] =
-            DrawOrder::new(false, &Vec3::new(self.pos.x, self.pos.y, 9.0), 0);
-
-        self.orders[1] =
-            DrawOrder::new(false, &Vec3::new(self.pos.x, self.pos.y, 5.0), 1);
         self.changed = false;
     }
 
@@ -218,7 +260,87 @@ impl Map {
             can_render: false,
             changed: true,
             camera_type: CameraType::None,
+            layer_tints: [Color::rgba(255, 255, 255, 255);
+                MapLayers::Count as usize],
+            tile_animations: AHashMap::default(),
+        }
+    }
+
+    /// Animates a tile using a [`TileAnimation`] registered with
+    /// [`MapRenderer::add_animation`]. The tile still needs to be set with
+    /// [`Map::set_tile`] as the animation only replaces its allocation id.
+    ///
+    /// # Arguments
+    /// - pos: X, Y and Layer of the tile.
+    /// - anim_id: Animation id or None to stop animating the tile.
+    ///
+    pub fn set_tile_animation(
+        &mut self,
+        pos: (u32, u32, u32),
+        anim_id: Option<usize>,
+    ) {
+        if pos.0 >= 32 || pos.1 >= 32 || pos.2 >= 9 {
+            return;
+        }
+
+        let index = (pos.0 + (pos.1 * 32) + (pos.2 * 1024)) as usize;
+
+        match anim_id {
+            Some(id) => self.tile_animations.insert(index, (id, 0)),
+            None => self.tile_animations.remove(&index),
+        };
+
+        self.changed = true;
+    }
+
+    /// Updates the current frame of every animated tile.
+    /// Marks the [`Map`] as changed if any frame changed.
+    /// Called by [`MapRenderer::map_update`].
+    ///
+    /// # Arguments
+    /// - animations: Registered [`TileAnimation`]'s.
+    /// - millsecs: Time used to pick the frame.
+    ///
+    pub fn update_animations(
+        &mut self,
+        animations: &slab::Slab<TileAnimation>,
+        millsecs: u64,
+    ) {
+        for (anim_id, frame) in self.tile_animations.values_mut() {
+            let next = animations
+                .get(*anim_id)
+                .and_then(|anim| anim.frame_at(millsecs))
+                .unwrap_or(0);
+
+            if *frame != next {
+                *frame = next;
+                self.changed = true;
+            }
+        }
+    }
+
+    /// Sets the Color multiplied into every tile of the [`MapLayers`].
+    /// Use this to tint or fade a whole layer without changing each tile.
+    /// An alpha below 255 renders the layer's buffer as transparent.
+    /// The tint is stored per tile vertex so each change rebuilds every tile
+    /// of the [`Map`] on the next update, the same as a [`Map::set_tile`].
+    ///
+    pub fn set_layer_tint(&mut self, layer: MapLayers, color: Color) {
+        if layer == MapLayers::Count {
+            return;
         }
+
+        self.layer_tints[layer as usize] = color;
+        self.changed = true;
+    }
+
+    /// Returns the Color multiplied into every tile of the [`MapLayers`].
+    ///
+    pub fn layer_tint(&self, layer: MapLayers) -> Color {
+        self.layer_tints
+            .get(layer as usize)
+            .copied()
+            .unwrap_or(Color::rgba(255, 255, 255, 255))
     }
 
     /// Unloades the [`Map`]'s buffer from the buffer store.
@@ -276,6 +398,56 @@ impl Map {
         self.changed = true;
     }
 
+    /// Flood fills all 4-connected tiles on the layer of start that match the
+    /// id and color of the tile at start with new_tile.
+    /// Does nothing if start is out of range or the tile already equals new_tile.
+    ///
+    /// # Arguments
+    /// - start: X, Y and Layer of the tile to start the fill from.
+    /// - new_tile: The [`TileData`] to replace the matching tiles with.
+    ///
+    pub fn flood_fill(&mut self, start: UVec3, new_tile: TileData) {
+        if start.x >= 32 || start.y >= 32 || start.z >= 9 {
+            return;
+        }
+
+        let original = self.get_tile((start.x, start.y, start.z));
+        let matches = |tile: &TileData, other: &TileData| {
+            tile.id == other.id && tile.color == other.color
+        };
+
+        if matches(&original, &new_tile) {
+            return;
+        }
+
+        let mut queue = VecDeque::with_capacity(64);
+        queue.push_back((start.x, start.y));
+
+        while let Some((x, y)) = queue.pop_front() {
+            if !matches(&self.get_tile((x, y, start.z)), &original) {
+                continue;
+            }
+
+            self.set_tile((x, y, start.z), new_tile);
+
+            if x > 0 {
+                queue.push_back((x - 1, y));
+            }
+
+            if x < 31 {
+                queue.push_back((x + 1, y));
+            }
+
+            if y > 0 {
+                queue.push_back((x, y - 1));
+            }
+
+            if y < 31 {
+                queue.push_back((x, y + 1));
+            }
+        }
+    }
+
     /// Used to check and update the [`Map`]'s Buffer for Rendering.
     /// Returns an Optional vec![Lower, Upper] [`OrderedIndex`] to use in Rendering.
     ///
@@ -297,4 +469,22 @@ impl Map {
             None
         }
     }
+
+    /// Returns the lower layers [`OrderedIndex`] to use in Rendering.
+    /// Call [`Map::update`] first so the Buffer and order are current.
+    /// Returns None if the [`Map`] can not render.
+    ///
+    pub fn lower_index(&self) -> Option<OrderedIndex> {
+        self.can_render
+            .then(|| OrderedIndex::new(self.orders[0], self.stores[0], 0))
+    }
+
+    /// Returns the upper layers [`OrderedIndex`] to use in Rendering.
+    /// Call [`Map::update`] first so the Buffer and order are current.
+    /// Returns None if the [`Map`] can not render.
+    ///
+    pub fn upper_index(&self) -> Option<OrderedIndex> {
+        self.can_render
+            .then(|| OrderedIndex::new(self.orders[1], self.stores[1], 0))
+    }
 }
@@ -0,0 +1,51 @@
+/// Ordered list of tile frames used to animate a [`crate::Map`] tile.
+/// Each frame is an [`crate::AtlasSet`] allocation id and how long it shows.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TileAnimation {
+    /// (Allocation id, Duration in millsecs) per frame.
+    pub frames: Vec<(usize, u32)>,
+}
+
+impl TileAnimation {
+    /// Creates a new [`TileAnimation`].
+    ///
+    /// # Arguments
+    /// - frames: (Allocation id, Duration in millsecs) per frame.
+    ///
+    pub fn new(frames: Vec<(usize, u32)>) -> Self {
+        Self { frames }
+    }
+
+    /// Returns the total length of the animation in millsecs.
+    ///
+    pub fn duration(&self) -> u64 {
+        self.frames.iter().map(|(_, time)| *time as u64).sum()
+    }
+
+    /// Returns the allocation id of the frame shown at the time.
+    /// The animation loops. Returns None if there are no frames.
+    ///
+    /// # Arguments
+    /// - millsecs: Time since the animation started.
+    ///
+    pub fn frame_at(&self, millsecs: u64) -> Option<usize> {
+        let duration = self.duration();
+
+        if duration == 0 {
+            return self.frames.first().map(|(id, _)| *id);
+        }
+
+        let mut time = millsecs % duration;
+
+        for (id, frame_time) in &self.frames {
+            if time < *frame_time as u64 {
+                return Some(*id);
+            }
+
+            time -= *frame_time as u64;
+        }
+
+        self.frames.last().map(|(id, _)| *id)
+    }
+}
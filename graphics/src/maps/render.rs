@@ -1,14 +1,27 @@
 use crate::{
     AsBufferPass, AtlasSet, GpuRenderer, GraphicsError, InstanceBuffer, Map,
     MapRenderPipeline, MapVertex, OrderedIndex, SetBuffers, StaticVertexBuffer,
+    TileAnimation,
 };
 use log::warn;
+use slab::Slab;
+
+/// Fewest changed [`Map`]'s each thread builds in [`MapRenderer::update_maps`].
+/// Fewer changed [`Map`]'s than this are built on the calling thread as
+/// spawning would cost more than it saves.
+///
+#[cfg(not(target_arch = "wasm32"))]
+const MIN_MAPS_PER_THREAD: usize = 4;
 
 /// Instance Buffer Setup for [`Map`]'s.
 ///
 pub struct MapRenderer {
     /// Instance Buffer holding all Rendering information for [`Map`]'s.
     pub buffer: InstanceBuffer<MapVertex>,
+    /// Registered [`TileAnimation`]'s used by [`Map::set_tile_animation`].
+    pub animations: Slab<TileAnimation>,
+    /// Time in millsecs used to pick [`TileAnimation`] frames.
+    pub animation_time: u64,
 }
 
 impl MapRenderer {
@@ -27,6 +40,8 @@ impl MapRenderer {
                 9_216 * map_count as usize,
                 144,
             ),
+            animations: Slab::new(),
+            animation_time: 0,
         })
     }
 
@@ -68,6 +83,10 @@ impl MapRenderer {
         atlas: &mut AtlasSet,
         buffer_layers: [usize; 2],
     ) {
+        if !map.tile_animations.is_empty() {
+            map.update_animations(&self.animations, self.animation_time);
+        }
+
         if let Some(indexs) = map.update(renderer, atlas) {
             for (id, order_index) in indexs.into_iter().enumerate() {
                 self.add_buffer_store(renderer, order_index, buffer_layers[id]);
@@ -75,6 +94,111 @@ impl MapRenderer {
         }
     }
 
+    /// Updates many [`Map`]'s and adds their [`OrderedIndex`]'s to staging.
+    /// Changed [`Map`]'s build their vertices across threads once there are
+    /// enough to split, then the Buffers are written on this thread so the
+    /// renderer is never shared. Frames with no changed [`Map`]'s never
+    /// spawn threads.
+    /// This must be done before [`MapRenderer::finalize`] in order for them to Render.
+    ///
+    /// # Arguments
+    /// - maps: [`Map`]'s we want to update and prepare for rendering.
+    /// - atlas: [`AtlasSet`] the [`Map`]'s need to render with.
+    /// - buffer_layers: The Buffer Layer's we want to add the Objects too.
+    ///
+    pub fn update_maps(
+        &mut self,
+        maps: &mut [Map],
+        renderer: &mut GpuRenderer,
+        atlas: &AtlasSet,
+        buffer_layers: [usize; 2],
+    ) {
+        for map in maps.iter_mut() {
+            if !map.tile_animations.is_empty() {
+                map.update_animations(&self.animations, self.animation_time);
+            }
+        }
+
+        let mut changed: Vec<&mut Map> = maps
+            .iter_mut()
+            .filter(|map| map.can_render && map.changed)
+            .collect();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let threads = std::thread::available_parallelism()
+                .map_or(1, |threads| threads.get());
+            let chunk_size =
+                changed.len().div_ceil(threads).max(MIN_MAPS_PER_THREAD);
+
+            if changed.len() > chunk_size && threads > 1 {
+                std::thread::scope(|scope| {
+                    for chunk in changed.chunks_mut(chunk_size) {
+                        scope.spawn(move || {
+                            for map in chunk {
+                                map.create_vertices(atlas);
+                            }
+                        });
+                    }
+                });
+            } else {
+                for map in changed.iter_mut() {
+                    map.create_vertices(atlas);
+                }
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        for map in changed.iter_mut() {
+            map.create_vertices(atlas);
+        }
+
+        for map in maps.iter_mut().filter(|map| map.can_render) {
+            if map.changed {
+                map.write_stores(renderer);
+            }
+
+            for (id, order_index) in [map.lower_index(), map.upper_index()]
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                self.add_buffer_store(renderer, order_index, buffer_layers[id]);
+            }
+        }
+    }
+
+    /// Registers a [`TileAnimation`] and returns its animation id.
+    ///
+    pub fn add_animation(&mut self, animation: TileAnimation) -> usize {
+        self.animations.insert(animation)
+    }
+
+    /// Removes a [`TileAnimation`]. Tiles still using it show their own tile.
+    ///
+    pub fn remove_animation(
+        &mut self,
+        anim_id: usize,
+    ) -> Option<TileAnimation> {
+        self.animations.try_remove(anim_id)
+    }
+
+    /// Returns a mutable [`TileAnimation`] to change its frames.
+    ///
+    pub fn get_animation_mut(
+        &mut self,
+        anim_id: usize,
+    ) -> Option<&mut TileAnimation> {
+        self.animations.get_mut(anim_id)
+    }
+
+    /// Sets the time in millsecs used to pick [`TileAnimation`] frames.
+    /// Call this each frame before [`MapRenderer::map_update`].
+    ///
+    pub fn set_animation_time(&mut self, millsecs: u64) {
+        self.animation_time = millsecs;
+    }
+
     /// Map does not use Clipping.
     pub fn use_clipping(&mut self) {
         warn!("Map does not use Clipping.");
@@ -95,6 +219,16 @@ where
         atlas: &'b AtlasSet,
         buffer_layer: usize,
     );
+
+    /// Renders every buffer layer of [`Map`]'s that have been processed and finalized.
+    /// Shared pipeline and bind state is only set once.
+    ///
+    fn render_all_maps(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b MapRenderer,
+        atlas: &'b AtlasSet,
+    );
 }
 
 impl<'a, 'b> RenderMap<'a, 'b> for wgpu::RenderPass<'a>
@@ -124,4 +258,35 @@ where
             }
         }
     }
+
+    fn render_all_maps(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b MapRenderer,
+        atlas: &'b AtlasSet,
+    ) {
+        if buffer.buffer.count() == 0 {
+            return;
+        }
+
+        let mut state_set = false;
+
+        for details in buffer.buffer.buffers.iter().flatten() {
+            if !state_set {
+                self.set_buffers(renderer.buffer_object.as_buffer_pass());
+                self.set_bind_group(1, atlas.bind_group(), &[]);
+                self.set_vertex_buffer(1, buffer.buffer.instances(None));
+                self.set_pipeline(
+                    renderer.get_pipelines(MapRenderPipeline).unwrap(),
+                );
+                state_set = true;
+            }
+
+            self.draw_indexed(
+                0..StaticVertexBuffer::index_count(),
+                0,
+                details.start..details.end,
+            );
+        }
+    }
 }
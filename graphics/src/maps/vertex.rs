@@ -11,6 +11,7 @@ pub struct MapVertex {
     pub tile_id: u32,
     pub texture_layer: u32,
     pub color: u32,
+    pub tint: u32,
     pub camera_type: u32,
 }
 
@@ -22,6 +23,7 @@ impl Default for MapVertex {
             tile_id: 0,
             texture_layer: 0,
             color: 0,
+            tint: 0,
             camera_type: 0,
         }
     }
@@ -29,7 +31,7 @@ impl Default for MapVertex {
 
 impl BufferLayout for MapVertex {
     fn attributes() -> Vec<wgpu::VertexAttribute> {
-        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32, 3 => Uint32, 4 => Uint32, 5 => Uint32, 6 => Uint32]
+        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32, 3 => Uint32, 4 => Uint32, 5 => Uint32, 6 => Uint32, 7 => Uint32]
             .to_vec()
     }
 
@@ -52,6 +54,6 @@ impl BufferLayout for MapVertex {
     }
 
     fn stride() -> usize {
-        std::mem::size_of::<[f32; 8]>()
+        std::mem::size_of::<[f32; 9]>()
     }
 }
@@ -3,7 +3,7 @@ mod pipeline;
 mod render;
 mod vertex;
 
-pub use lyon::tessellation::{FillOptions, StrokeOptions};
+pub use lyon::tessellation::{FillOptions, LineCap, LineJoin, StrokeOptions};
 pub use meshs::*;
 pub use pipeline::*;
 pub use render::*;
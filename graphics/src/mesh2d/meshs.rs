@@ -4,11 +4,12 @@ use crate::{
 };
 use cosmic_text::Color;
 use lyon::{
-    lyon_tessellation::{FillOptions, StrokeOptions},
+    lyon_tessellation::{FillOptions, LineCap, LineJoin, StrokeOptions},
     math::Point as LPoint,
     path::Polygon,
     tessellation as tess,
 };
+use std::ops::Range;
 
 /// Mode in how we will Create the Mesh's vertex layout.
 #[derive(Debug, Copy, Clone)]
@@ -20,10 +21,68 @@ pub enum DrawMode {
 }
 
 impl DrawMode {
+    /// Creates a Stroke [`DrawMode`] using lyon's default joins and caps.
+    ///
     pub fn stroke(width: f32) -> DrawMode {
         DrawMode::Stroke(StrokeOptions::default().with_line_width(width))
     }
 
+    /// Creates a Stroke [`DrawMode`] from custom [`StrokeOptions`].
+    ///
+    pub fn stroke_with(options: StrokeOptions) -> DrawMode {
+        DrawMode::Stroke(options)
+    }
+
+    /// Sets the [`LineJoin`] used between Stroke segments.
+    /// Does nothing if the [`DrawMode`] is Fill.
+    ///
+    pub fn with_line_join(self, line_join: LineJoin) -> DrawMode {
+        match self {
+            DrawMode::Stroke(options) => {
+                DrawMode::Stroke(options.with_line_join(line_join))
+            }
+            DrawMode::Fill(_) => self,
+        }
+    }
+
+    /// Sets the start and end [`LineCap`] of the Stroke.
+    /// Does nothing if the [`DrawMode`] is Fill.
+    ///
+    pub fn with_line_cap(self, line_cap: LineCap) -> DrawMode {
+        match self {
+            DrawMode::Stroke(options) => {
+                DrawMode::Stroke(options.with_line_cap(line_cap))
+            }
+            DrawMode::Fill(_) => self,
+        }
+    }
+
+    /// Sets the miter limit of the Stroke which is used by [`LineJoin::Miter`].
+    /// Does nothing if the [`DrawMode`] is Fill.
+    ///
+    pub fn with_miter_limit(self, limit: f32) -> DrawMode {
+        match self {
+            DrawMode::Stroke(options) => {
+                DrawMode::Stroke(options.with_miter_limit(limit))
+            }
+            DrawMode::Fill(_) => self,
+        }
+    }
+
+    /// Sets the tolerance used when flattening curves into triangles.
+    /// Lower values give smoother curves with more triangles.
+    ///
+    pub fn with_tolerance(self, tolerance: f32) -> DrawMode {
+        match self {
+            DrawMode::Stroke(options) => {
+                DrawMode::Stroke(options.with_tolerance(tolerance))
+            }
+            DrawMode::Fill(options) => {
+                DrawMode::Fill(options.with_tolerance(tolerance))
+            }
+        }
+    }
+
     pub fn fill() -> DrawMode {
         DrawMode::Fill(FillOptions::default())
     }
@@ -50,8 +109,13 @@ pub struct Mesh2D {
     pub high_index: u32,
     /// Overides the absolute order values based on position.
     pub order_override: Option<Vec3>,
+    /// Explicit key to sort by instead of the position.
+    pub sort_key: Option<u64>,
     // if anything got updated we need to update the buffers too.
     pub changed: bool,
+    /// Vertex Range changed by [`Mesh2D::update_vertices`] that still needs
+    /// to be written into the buffer store.
+    dirty_vertices: Option<Range<usize>>,
 }
 
 impl Mesh2D {
@@ -69,6 +133,8 @@ impl Mesh2D {
             high_index: 0,
             render_layer,
             order_override: None,
+            sort_key: None,
+            dirty_vertices: None,
         }
     }
 
@@ -78,6 +144,15 @@ impl Mesh2D {
         renderer.remove_buffer(self.vbo_store_id);
     }
 
+    /// Sets an explicit key the [`Mesh2D`] sorts by instead of its position.
+    /// Keyed objects draw after unkeyed ones on the same order layer.
+    ///
+    pub fn set_sort_key(&mut self, sort_key: Option<u64>) -> &mut Self {
+        self.changed = true;
+        self.sort_key = sort_key;
+        self
+    }
+
     /// Updates the [`Mesh2D`]'s order_override.
     ///
     pub fn set_order_override(
@@ -156,6 +231,74 @@ impl Mesh2D {
         self.vertices.clear();
         self.indices.clear();
         self.high_index = 0;
+        self.dirty_vertices = None;
+    }
+
+    /// Replaces a Range of the [`Mesh2D`]'s vertices. On the next update only
+    /// the changed region gets written into the buffer store so the gpu
+    /// receives a partial upload.
+    ///
+    /// # Arguments
+    /// - range: Range of vertex indices to replace.
+    /// - verts: New vertices. Must match the length of range.
+    ///
+    pub fn update_vertices(
+        &mut self,
+        range: Range<usize>,
+        verts: &[Mesh2DVertex],
+    ) -> Result<(), GraphicsError> {
+        if range.end > self.vertices.len() || range.len() != verts.len() {
+            return Err(GraphicsError::Other(OtherError::new(
+                "vertex range does not match the Mesh2D's vertices",
+            )));
+        }
+
+        self.vertices[range.clone()].copy_from_slice(verts);
+        self.dirty_vertices = Some(match self.dirty_vertices.take() {
+            Some(dirty) => {
+                dirty.start.min(range.start)..dirty.end.max(range.end)
+            }
+            None => range,
+        });
+        Ok(())
+    }
+
+    /// Writes the dirty vertex Range into the buffer store. Falls back to a
+    /// full rebuild if the store no longer matches the vertex count.
+    ///
+    fn write_dirty_vertices(&mut self, renderer: &mut GpuRenderer) {
+        let Some(range) = self.dirty_vertices.take() else {
+            return;
+        };
+
+        let stride = std::mem::size_of::<Mesh2DVertex>();
+
+        if let Some(store) = renderer.get_buffer_mut(self.vbo_store_id) {
+            if store.store.len() != self.vertices.len() * stride {
+                self.create_quad(renderer);
+                return;
+            }
+
+            let mut updated = Vec::with_capacity(range.len());
+
+            for vertex in &self.vertices[range.clone()] {
+                let mut v = *vertex;
+
+                if Color(v.color).a() < 255 {
+                    self.order.alpha = true
+                }
+
+                v.position[0] += self.position.x;
+                v.position[1] += self.position.y;
+                updated.push(v);
+            }
+
+            let bytes: &[u8] = bytemuck::cast_slice(&updated);
+            let byte_range = range.start * stride..range.end * stride;
+
+            store.store[byte_range.clone()].copy_from_slice(bytes);
+            store.mark_dirty(byte_range);
+        }
     }
 
     /// Sets the [`Mesh2D`]'s Position.
@@ -180,6 +323,15 @@ impl Mesh2D {
         let mut alpha = false;
 
         if let Some(store) = renderer.get_buffer_mut(self.vbo_store_id) {
+            // Nothing to draw so leave the store empty for it to be skipped.
+            if self.is_empty() {
+                store.store.clear();
+                store.indexs.clear();
+                store.changed = true;
+                self.order = DrawOrder::default();
+                return;
+            }
+
             let mut verticies = Vec::with_capacity(self.vertices.len());
 
             for vertex in &self.vertices {
@@ -208,7 +360,8 @@ impl Mesh2D {
             None => self.position,
         };
 
-        self.order = DrawOrder::new(alpha, &order_pos, self.render_layer);
+        self.order = DrawOrder::new(alpha, &order_pos, self.render_layer)
+            .with_sort_key(self.sort_key);
     }
 
     /// Used to check and update the vertex array.
@@ -218,11 +371,20 @@ impl Mesh2D {
         if self.changed {
             self.create_quad(renderer);
             self.changed = false;
+            self.dirty_vertices = None;
+        } else if self.dirty_vertices.is_some() {
+            self.write_dirty_vertices(renderer);
         }
 
         OrderedIndex::new(self.order, self.vbo_store_id, self.high_index)
     }
 
+    /// Returns true if the [`Mesh2D`] has no vertices or indices to draw.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() || self.indices.is_empty()
+    }
+
     /// Checks if Mouse position is within the [`Mesh2D`]'s Bounds.
     pub fn check_mouse_bounds(&self, mouse_pos: Vec2) -> bool {
         mouse_pos[0] > self.position.x
@@ -243,6 +405,9 @@ pub struct Mesh2DBuilder {
     pub z: f32,
     pub high_index: u32,
     pub camera_type: CameraType,
+    /// Tolerance used by the `*_auto` shapes. When None it is derived from
+    /// each shape's radius.
+    pub tolerance: Option<f32>,
 }
 
 impl Default for Mesh2DBuilder {
@@ -255,6 +420,7 @@ impl Default for Mesh2DBuilder {
             z: 1.0,
             high_index: 0,
             camera_type: CameraType::None,
+            tolerance: None,
         }
     }
 }
@@ -269,6 +435,7 @@ impl Mesh2DBuilder {
         self.z = 1.0;
         self.high_index = 0;
         self.camera_type = CameraType::None;
+        self.tolerance = None;
     }
 
     /// Creates a new [`Mesh2DBuilder`] with [`CameraType`].
@@ -280,6 +447,34 @@ impl Mesh2DBuilder {
         }
     }
 
+    /// Sets the default tolerance used by the `*_auto` shapes.
+    ///
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.set_tolerance(Some(tolerance));
+        self
+    }
+
+    /// Sets the default tolerance used by the `*_auto` shapes.
+    /// None derives it from each shape's radius.
+    ///
+    pub fn set_tolerance(&mut self, tolerance: Option<f32>) -> &mut Self {
+        assert!(
+            tolerance.is_none_or(|tolerance| tolerance > 0.0),
+            "Tolerances <= 0 are invalid"
+        );
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Returns the tolerance the `*_auto` shapes use for a radius.
+    /// This is the set tolerance or radius / 200 so larger shapes stay
+    /// smooth without adding triangles to small ones.
+    ///
+    pub fn auto_tolerance(&self, radius: f32) -> f32 {
+        self.tolerance
+            .unwrap_or_else(|| (radius.abs() / 200.0).max(0.01))
+    }
+
     /// Sets the [`Mesh2DBuilder`]'s offset positions which is used during appending or building to Mesh2D.
     /// This does not affect the finalization Phase.
     ///
@@ -318,6 +513,9 @@ impl Mesh2DBuilder {
     }
 
     /// Draws a Line within the [`Mesh2DBuilder`] vertex buffer.
+    /// Uses the default joins and caps. For round or custom joins and caps use
+    /// [`Mesh2DBuilder::polyline`] with [`DrawMode::stroke_with`] or
+    /// [`DrawMode::with_line_join`] and [`DrawMode::with_line_cap`].
     ///
     pub fn line(
         &mut self,
@@ -372,6 +570,20 @@ impl Mesh2DBuilder {
         Ok(self)
     }
 
+    /// Draws a Circle using [`Mesh2DBuilder::auto_tolerance`].
+    ///
+    pub fn circle_auto(
+        &mut self,
+        mode: DrawMode,
+        point: Vec2,
+        radius: f32,
+        z: f32,
+        color: Color,
+    ) -> Result<&mut Self, GraphicsError> {
+        let tolerance = self.auto_tolerance(radius);
+        self.circle(mode, point, radius, tolerance, z, color)
+    }
+
     /// Draws an Ellipse within the [`Mesh2DBuilder`] vertex buffer.
     ///
     #[allow(clippy::too_many_arguments)]
@@ -423,7 +635,162 @@ impl Mesh2DBuilder {
         Ok(self)
     }
 
+    /// Draws an Ellipse using [`Mesh2DBuilder::auto_tolerance`] of the
+    /// larger radius.
+    ///
+    pub fn ellipse_auto(
+        &mut self,
+        mode: DrawMode,
+        point: Vec2,
+        radius1: f32,
+        radius2: f32,
+        z: f32,
+        color: Color,
+    ) -> Result<&mut Self, GraphicsError> {
+        let tolerance = self.auto_tolerance(radius1.abs().max(radius2.abs()));
+        self.ellipse(mode, point, radius1, radius2, tolerance, z, color)
+    }
+
+    /// Draws an Arc within the [`Mesh2DBuilder`] vertex buffer.
+    /// Angles are in radians where 0 points along +X and a positive sweep
+    /// turns towards +Y. Negative sweeps go the other way and sweeps are
+    /// limited to one full circle. Filling an Arc closes it with a straight
+    /// line between its ends.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc(
+        &mut self,
+        mode: DrawMode,
+        center: Vec2,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        tolerance: f32,
+        z: f32,
+        color: Color,
+    ) -> Result<&mut Self, GraphicsError> {
+        let path =
+            Self::arc_path(center, radius, start_angle, sweep_angle, false);
+
+        self.tessellate_path(mode, path, tolerance, z, color)
+    }
+
+    /// Draws a Pie slice within the [`Mesh2DBuilder`] vertex buffer.
+    /// Like [`Mesh2DBuilder::arc`] but the ends close through the center
+    /// to make a wedge. A full circle sweep draws a full circle.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn pie(
+        &mut self,
+        mode: DrawMode,
+        center: Vec2,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        tolerance: f32,
+        z: f32,
+        color: Color,
+    ) -> Result<&mut Self, GraphicsError> {
+        if sweep_angle.abs() >= std::f32::consts::TAU {
+            return self.circle(mode, center, radius, tolerance, z, color);
+        }
+
+        let path =
+            Self::arc_path(center, radius, start_angle, sweep_angle, true);
+
+        self.tessellate_path(mode, path, tolerance, z, color)
+    }
+
+    /// Builds the Path of an arc. Returns None if there is nothing to draw.
+    ///
+    fn arc_path(
+        center: Vec2,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        through_center: bool,
+    ) -> Option<lyon::path::Path> {
+        let sweep_angle =
+            sweep_angle.clamp(-std::f32::consts::TAU, std::f32::consts::TAU);
+
+        if radius <= 0.0 || sweep_angle == 0.0 {
+            return None;
+        }
+
+        let center = tess::math::point(center.x, center.y);
+        let arc = lyon::geom::Arc {
+            center,
+            radii: tess::math::vector(radius, radius),
+            start_angle: tess::math::Angle::radians(start_angle),
+            sweep_angle: tess::math::Angle::radians(sweep_angle),
+            x_rotation: tess::math::Angle::radians(0.0),
+        };
+        let full_circle = sweep_angle.abs() >= std::f32::consts::TAU;
+        let mut builder = lyon::path::Path::builder();
+
+        if through_center {
+            builder.begin(center);
+            builder.line_to(arc.from());
+        } else {
+            builder.begin(arc.from());
+        }
+
+        arc.for_each_quadratic_bezier(&mut |curve| {
+            builder.quadratic_bezier_to(curve.ctrl, curve.to);
+        });
+
+        builder.end(through_center || full_circle);
+        Some(builder.build())
+    }
+
+    /// Tessellates a Path into the [`Mesh2DBuilder`] vertex buffer.
+    ///
+    fn tessellate_path(
+        &mut self,
+        mode: DrawMode,
+        path: Option<lyon::path::Path>,
+        tolerance: f32,
+        z: f32,
+        color: Color,
+    ) -> Result<&mut Self, GraphicsError> {
+        assert!(tolerance > 0.0, "Tolerances <= 0 are invalid");
+
+        let Some(path) = path else {
+            return Ok(self);
+        };
+
+        let buffers = &mut self.buffer;
+        let vb = VertexBuilder {
+            z,
+            color,
+            camera: self.camera_type as u32,
+        };
+
+        match mode {
+            DrawMode::Fill(fill_options) => {
+                let mut tessellator = tess::FillTessellator::new();
+                tessellator.tessellate_path(
+                    &path,
+                    &fill_options.with_tolerance(tolerance),
+                    &mut tess::BuffersBuilder::new(buffers, vb),
+                )?;
+            }
+            DrawMode::Stroke(options) => {
+                let mut tessellator = tess::StrokeTessellator::new();
+                tessellator.tessellate_path(
+                    &path,
+                    &options.with_tolerance(tolerance),
+                    &mut tess::BuffersBuilder::new(buffers, vb),
+                )?;
+            }
+        };
+
+        Ok(self)
+    }
+
     /// Draws an Polyline within the [`Mesh2DBuilder`] vertex buffer.
+    /// When using a Stroke [`DrawMode`] the joins, caps and miter limit can be set
+    /// like `DrawMode::stroke(4.0).with_line_join(LineJoin::Round).with_line_cap(LineCap::Round)`.
     ///
     pub fn polyline(
         &mut self,
@@ -433,9 +800,10 @@ impl Mesh2DBuilder {
         color: Color,
     ) -> Result<&mut Self, GraphicsError> {
         if points.len() < 2 {
-            return Err(GraphicsError::Other(OtherError::new(
-                "MeshBuilder::polyline() got a list of < 2 points",
-            )));
+            return Err(GraphicsError::TooFewPoints {
+                got: points.len(),
+                needed: 2,
+            });
         }
 
         self.polyline_inner(mode, points, false, z, color)
@@ -451,9 +819,10 @@ impl Mesh2DBuilder {
         color: Color,
     ) -> Result<&mut Self, GraphicsError> {
         if points.len() < 3 {
-            return Err(GraphicsError::Other(OtherError::new(
-                "MeshBuilder::polygon() got a list of < 3 points",
-            )));
+            return Err(GraphicsError::TooFewPoints {
+                got: points.len(),
+                needed: 3,
+            });
         }
 
         self.polyline_inner(mode, points, true, z, color)
@@ -609,6 +978,21 @@ impl Mesh2DBuilder {
         Ok(self)
     }
 
+    /// Draws a Rounded Rectangle using [`Mesh2DBuilder::auto_tolerance`]
+    /// instead of the [`DrawMode`]'s tolerance.
+    ///
+    pub fn rounded_rectangle_auto(
+        &mut self,
+        mode: DrawMode,
+        bounds: Vec4,
+        z: f32,
+        radius: f32,
+        color: Color,
+    ) -> Result<&mut Self, GraphicsError> {
+        let mode = mode.with_tolerance(self.auto_tolerance(radius));
+        self.rounded_rectangle(mode, bounds, z, radius, color)
+    }
+
     /// Draws an Triangle within the [`Mesh2DBuilder`] vertex buffer.
     ///
     pub fn triangles(
@@ -619,9 +1003,9 @@ impl Mesh2DBuilder {
     ) -> Result<&mut Self, GraphicsError> {
         {
             if (triangles.len() % 3) != 0 {
-                return Err(GraphicsError::Other(OtherError::new(
-                    "Called MeshBuilder::triangles() with points that have a length not a multiple of 3.",
-                )));
+                return Err(GraphicsError::InvalidPointCount {
+                    len: triangles.len(),
+                });
             }
             let tris = triangles
                 .iter()
@@ -20,9 +20,10 @@ impl PipeLineLayout for Mesh2DRenderPipeline {
         let shader = gpu_device.device().create_shader_module(
             wgpu::ShaderModuleDescriptor {
                 label: Some("Shader"),
-                source: wgpu::ShaderSource::Wgsl(
-                    include_str!("../shaders/2dmeshshader.wgsl").into(),
-                ),
+                source: wgpu::ShaderSource::Wgsl(crate::load_shader(
+                    "2dmeshshader.wgsl",
+                    include_str!("../shaders/2dmeshshader.wgsl"),
+                )),
             },
         );
 
@@ -59,7 +60,7 @@ impl PipeLineLayout for Mesh2DRenderPipeline {
                     conservative: false,
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth32Float,
+                    format: gpu_device.depth_format(),
                     depth_write_enabled: true,
                     depth_compare: wgpu::CompareFunction::LessEqual,
                     stencil: wgpu::StencilState::default(),
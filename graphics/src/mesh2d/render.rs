@@ -55,7 +55,9 @@ impl Mesh2DRenderer {
     ) {
         let index = mesh.update(renderer);
 
-        self.add_buffer_store(renderer, index, buffer_layer);
+        if !mesh.is_empty() {
+            self.add_buffer_store(renderer, index, buffer_layer);
+        }
     }
 
     /// Sets the Instance Buffer to enable Rendering With Scissor Clipping.
@@ -81,6 +83,16 @@ where
         system: &'b System<Controls>,
         buffer_layer: usize,
     );
+
+    /// Renders every buffer layer of [`Mesh2D`]'s that have been processed and finalized.
+    /// Respects clipped and unclipped buffers.
+    ///
+    fn render_all_2dmeshs(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b Mesh2DRenderer,
+        system: &'b System<Controls>,
+    );
 }
 
 impl<'a, 'b, Controls> RenderMesh2D<'a, 'b, Controls> for wgpu::RenderPass<'a>
@@ -157,4 +169,15 @@ where
             }
         }
     }
+
+    fn render_all_2dmeshs(
+        &mut self,
+        renderer: &'b GpuRenderer,
+        buffer: &'b Mesh2DRenderer,
+        system: &'b System<Controls>,
+    ) {
+        for buffer_layer in 0..buffer.vbos.buffers.len() {
+            self.render_2dmeshs(renderer, buffer, system, buffer_layer);
+        }
+    }
 }
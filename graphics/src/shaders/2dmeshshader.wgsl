@@ -8,12 +8,25 @@ struct Global {
     seconds: f32,
     manual_view: mat4x4<f32>,
     manual_scale: f32,
+    srgb_encode: u32,
 };
 
 @group(0)
 @binding(0)
 var<uniform> global: Global;
 
+// Encodes to sRGB when the surface format is linear. See System srgb_encode.
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if (global.srgb_encode == 0u) {
+        return color;
+    }
+
+    let rgb = max(color.rgb, vec3<f32>(0.0));
+    let low = rgb * 12.92;
+    let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return vec4<f32>(select(high, low, rgb <= vec3<f32>(0.0031308)), color.a);
+}
+
 struct VertexInput {
     @builtin(vertex_index) vertex_idx: u32,
     @location(0) position: vec3<f32>,
@@ -88,5 +101,5 @@ fn vertex(
 
 @fragment
 fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
-    return vertex.color;
+    return encode_output(vertex.color);
 }
\ No newline at end of file
@@ -8,12 +8,25 @@ struct Global {
     seconds: f32,
     manual_view: mat4x4<f32>,
     manual_scale: f32,
+    srgb_encode: u32,
 };
 
 @group(0)
 @binding(0)
 var<uniform> global: Global;
 
+// Encodes to sRGB when the surface format is linear. See System srgb_encode.
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if (global.srgb_encode == 0u) {
+        return color;
+    }
+
+    let rgb = max(color.rgb, vec3<f32>(0.0));
+    let low = rgb * 12.92;
+    let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return vec4<f32>(select(high, low, rgb <= vec3<f32>(0.0031308)), color.a);
+}
+
 struct VertexInput {
     @builtin(vertex_index) vertex_idx: u32,
     @location(0) v_pos: vec2<f32>,
@@ -28,6 +41,7 @@ struct VertexInput {
     @location(9) layer: i32,
     @location(10) angle: f32,
     @location(11) flip_style: u32,
+    @location(12) alpha_mode: u32,
 };
 
 struct VertexOutput {
@@ -40,6 +54,7 @@ struct VertexOutput {
     @location(5) layer: i32,
     @location(6) time: u32,
     @location(7) animate: u32,
+    @location(8) alpha_mode: u32,
 };
 
 struct Axises {
@@ -63,6 +78,31 @@ fn srgb_to_linear(c: f32) -> f32 {
     }
 }
 
+// 1 is a premultiplied linear Texture and 2 a premultiplied sRGB Texture.
+// sRGB Textures were premultiplied before being encoded so they get
+// divided in the encoded space to match.
+fn unpremultiply(color: vec4<f32>, alpha_mode: u32) -> vec4<f32> {
+    if (alpha_mode == 0u || color.a <= 0.0) {
+        return color;
+    }
+
+    if (alpha_mode == 2u) {
+        let rgb = max(color.rgb, vec3<f32>(0.0));
+        let low = rgb * 12.92;
+        let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+        let encoded = min(select(high, low, rgb <= vec3<f32>(0.0031308)) / color.a, vec3<f32>(1.0));
+
+        return vec4<f32>(
+            srgb_to_linear(encoded.r),
+            srgb_to_linear(encoded.g),
+            srgb_to_linear(encoded.b),
+            color.a,
+        );
+    }
+
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
         srgb_to_linear(f32((color & 0xff0000u) >> 16u) / 255.0),
@@ -244,6 +284,7 @@ fn vertex(
     result.size = fsize;
     result.animate = vertex.animate;
     result.time = vertex.time;
+    result.alpha_mode = vertex.alpha_mode;
     return result;
 }
 
@@ -259,11 +300,11 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         vertex.animate > 0u
     );
 
-    let object_color = textureSampleLevel(tex, tex_sample ,coords / vertex.size, vertex.layer, 1.0) * vertex.col;
+    let object_color = unpremultiply(textureSampleLevel(tex, tex_sample ,coords / vertex.size, vertex.layer, 1.0), vertex.alpha_mode) * vertex.col;
 
     if (object_color.a <= 0.0) {
         discard;
     }
 
-    return object_color;
+    return encode_output(object_color);
 }
\ No newline at end of file
@@ -8,6 +8,7 @@ struct Global {
     seconds: f32,
     manual_view: mat4x4<f32>,
     manual_scale: f32,
+    srgb_encode: u32,
 };
 
 struct AreaLights {
@@ -43,6 +44,18 @@ struct DirLights {
 @binding(0)
 var<uniform> global: Global;
 
+// Encodes to sRGB when the surface format is linear. See System srgb_encode.
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if (global.srgb_encode == 0u) {
+        return color;
+    }
+
+    let rgb = max(color.rgb, vec3<f32>(0.0));
+    let low = rgb * 12.92;
+    let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return vec4<f32>(select(high, low, rgb <= vec3<f32>(0.0031308)), color.a);
+}
+
 struct VertexInput {
     @builtin(vertex_index) vertex_idx: u32,
     @location(0) v_pos: vec2<f32>,
@@ -120,6 +133,8 @@ fn vertex(
     return result;
 }
 
+// Falloff math is mirrored in lights/falloff.rs for Lights::light_level_at.
+// Keep both in sync.
 fn fade(d: f32, x0: f32, x1: f32, c: f32, w: f32) -> f32 {
    let w1 = max(0.000001, w);
    let sD = 1.0 / (1.0 + exp(-(c-d)/w1));
@@ -290,5 +305,5 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return col;
+    return encode_output(col);
 }
\ No newline at end of file
@@ -8,12 +8,25 @@ struct Global {
     seconds: f32,
     manual_view: mat4x4<f32>,
     manual_scale: f32,
+    srgb_encode: u32,
 };
 
 @group(0)
 @binding(0)
 var<uniform> global: Global;
 
+// Encodes to sRGB when the surface format is linear. See System srgb_encode.
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if (global.srgb_encode == 0u) {
+        return color;
+    }
+
+    let rgb = max(color.rgb, vec3<f32>(0.0));
+    let low = rgb * 12.92;
+    let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return vec4<f32>(select(high, low, rgb <= vec3<f32>(0.0031308)), color.a);
+}
+
 struct VertexInput {
     @builtin(vertex_index) vertex_idx: u32,
     @location(0) v_pos: vec2<f32>,
@@ -22,7 +35,8 @@ struct VertexInput {
     @location(3) tile_id: u32,
     @location(4) texture_layer: u32,
     @location(5) color: u32,
-    @location(6) camera_type: u32,
+    @location(6) tint: u32,
+    @location(7) camera_type: u32,
 };
 
 struct VertexOutput {
@@ -120,7 +134,7 @@ fn vertex(
         }
     }
 
-    result.color = unpack_color(vertex.color);
+    result.color = unpack_color(vertex.color) * unpack_color(vertex.tint);
 
     let id = global.seconds / (f32(250) / 1000.0);
     let frame = u32(floor(id % f32(4)));
@@ -154,6 +168,6 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return color;
+    return encode_output(color);
 }
 
@@ -8,12 +8,25 @@ struct Global {
     seconds: f32,
     manual_view: mat4x4<f32>,
     manual_scale: f32,
+    srgb_encode: u32,
 };
 
 @group(0)
 @binding(0)
 var<uniform> global: Global;
 
+// Encodes to sRGB when the surface format is linear. See System srgb_encode.
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if (global.srgb_encode == 0u) {
+        return color;
+    }
+
+    let rgb = max(color.rgb, vec3<f32>(0.0));
+    let low = rgb * 12.92;
+    let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return vec4<f32>(select(high, low, rgb <= vec3<f32>(0.0031308)), color.a);
+}
+
 struct VertexInput {
     @builtin(vertex_index) vertex_idx: u32,
     @location(0) v_pos: vec2<f32>,
@@ -26,6 +39,9 @@ struct VertexInput {
     @location(7) layer: u32,
     @location(8) radius: f32,
     @location(9) camera_type: u32,
+    @location(10) aa: u32,
+    @location(11) alpha_mode: u32,
+    @location(12) fill: u32,
 };
 
 struct VertexOutput {
@@ -40,6 +56,9 @@ struct VertexOutput {
     @location(8) radius: f32,
     @location(9) layer: i32,
     @location(10) tex_size: vec2<f32>,
+    @location(11) aa: u32,
+    @location(12) alpha_mode: u32,
+    @location(13) fill: u32,
 };
 
 @group(1)
@@ -66,6 +85,31 @@ fn srgb_to_linear(c: f32) -> f32 {
     }
 }
 
+// 1 is a premultiplied linear Texture and 2 a premultiplied sRGB Texture.
+// sRGB Textures were premultiplied before being encoded so they get
+// divided in the encoded space to match.
+fn unpremultiply(color: vec4<f32>, alpha_mode: u32) -> vec4<f32> {
+    if (alpha_mode == 0u || color.a <= 0.0) {
+        return color;
+    }
+
+    if (alpha_mode == 2u) {
+        let rgb = max(color.rgb, vec3<f32>(0.0));
+        let low = rgb * 12.92;
+        let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+        let encoded = min(select(high, low, rgb <= vec3<f32>(0.0031308)) / color.a, vec3<f32>(1.0));
+
+        return vec4<f32>(
+            srgb_to_linear(encoded.r),
+            srgb_to_linear(encoded.g),
+            srgb_to_linear(encoded.b),
+            color.a,
+        );
+    }
+
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
         srgb_to_linear(f32((color & 0xff0000u) >> 16u) / 255.0),
@@ -152,6 +196,9 @@ fn vertex(
     result.radius = vertex.radius;
     result.tex_size = fsize;
     result.layer = i32(vertex.layer);
+    result.aa = vertex.aa;
+    result.alpha_mode = vertex.alpha_mode;
+    result.fill = vertex.fill;
     result.color = unpack_color(vertex.color);
     result.border_color = unpack_color(vertex.border_color);
     return result;
@@ -184,10 +231,12 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
 
     let c1 = select(
         vec4<f32>(0.0), 
-        textureSampleLevel(tex, tex_sample, coords, vertex.layer, 1.0),
+        unpremultiply(textureSampleLevel(tex, tex_sample, coords, vertex.layer, 1.0), vertex.alpha_mode),
         vertex.container_data[2] > 0.0 && vertex.container_data[3] > 0.0
     );
-    let container_color = select(vertex.color, c1  * vertex.color, vertex.container_data[2] > 0.0 && vertex.container_data[3] > 0.0);
+    let filled_color = select(vertex.color, c1  * vertex.color, vertex.container_data[2] > 0.0 && vertex.container_data[3] > 0.0);
+    // Without a fill the inside fades to a clear border color so only the border band renders.
+    let container_color = select(vec4<f32>(vertex.border_color.rgb, 0.0), filled_color, vertex.fill > 0u);
     let radius = vertex.radius;
     let clippy = vec2<f32>(vertex.clip_position.x, global.size.y - vertex.clip_position.y);
     let border: f32 = max(radius - vertex.border_width, 0.0);
@@ -197,11 +246,18 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
             vertex.size - vec2<f32>(vertex.border_width * 2.0), 
             border 
         );
-    let border_mix: f32 = smoothstep(
-            max(border - 0.5, 0.0),
-            border + 0.5,
+    let aa = vertex.aa > 0u;
+    // Smooth over the pixel footprint so edges stay soft at any scale.
+    let border_edge = max(fwidth(distance) * 0.5, 0.0001);
+    let border_mix: f32 = select(
+        f32(distance > border),
+        smoothstep(
+            max(border - border_edge, 0.0),
+            border + border_edge,
             distance
-        );
+        ),
+        aa
+    );
     let mixed_color: vec4<f32> = select(
         container_color,
         mix(container_color, vertex.border_color, vec4<f32>(border_mix)), 
@@ -213,15 +269,21 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         vertex.size,
         radius
     );
-    let radius_alpha: f32 = 1.0 - smoothstep(
-        max(radius - 0.5, 0.0),
-        radius + 0.5,
-        dist);
+    let radius_edge = max(fwidth(dist) * 0.5, 0.0001);
+    let radius_alpha: f32 = 1.0 - select(
+        f32(dist > radius),
+        smoothstep(
+            max(radius - radius_edge, 0.0),
+            radius + radius_edge,
+            dist
+        ),
+        aa
+    );
     let alpha = mixed_color.a * radius_alpha;
 
     if (alpha <= 0.0) {
         discard;
     }
 
-    return vec4<f32>(mixed_color.r, mixed_color.g, mixed_color.b, alpha);
+    return encode_output(vec4<f32>(mixed_color.r, mixed_color.g, mixed_color.b, alpha));
 }
\ No newline at end of file
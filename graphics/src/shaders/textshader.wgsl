@@ -8,12 +8,25 @@ struct Global {
     seconds: f32,
     manual_view: mat4x4<f32>,
     manual_scale: f32,
+    srgb_encode: u32,
 };
 
 @group(0)
 @binding(0)
 var<uniform> global: Global;
 
+// Encodes to sRGB when the surface format is linear. See System srgb_encode.
+fn encode_output(color: vec4<f32>) -> vec4<f32> {
+    if (global.srgb_encode == 0u) {
+        return color;
+    }
+
+    let rgb = max(color.rgb, vec3<f32>(0.0));
+    let low = rgb * 12.92;
+    let high = 1.055 * pow(rgb, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return vec4<f32>(select(high, low, rgb <= vec3<f32>(0.0031308)), color.a);
+}
+
 struct VertexInput {
     @builtin(vertex_index) vertex_idx: u32,
     @location(0) v_pos: vec2<f32>,
@@ -24,6 +37,9 @@ struct VertexInput {
     @location(5) color: u32,
     @location(6) camera_type: u32,
     @location(7) is_color: u32,
+    @location(8) atlas: u32,
+    @location(9) transform: vec4<f32>,
+    @location(10) pivot: vec2<f32>,
 };
 
 struct VertexOutput {
@@ -101,6 +117,9 @@ fn vertex(
         }
     }
 
+    let transform = mat2x2<f32>(vertex.transform.xy, vertex.transform.zw);
+    pos = vec3<f32>(vertex.pivot + transform * (pos.xy - vertex.pivot), pos.z);
+
     switch vertex.camera_type {
         case 1u: {
             result.clip_position = (global.proj * global.view) * vec4<f32>(pos, 1.0);
@@ -142,6 +161,11 @@ fn vertex(
 // Fragment shader
 @fragment
 fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
+    // Solid background quads for highlighted spans.
+    if vertex.is_color == 2u {
+        return encode_output(vertex.color);
+    }
+
      let object_color = select(
         textureSampleLevel(tex, tex_sample, vertex.uv.xy, vertex.layer, 1.0),
         textureSampleLevel(emoji_tex, emoji_tex_sample, vertex.uv.xy, vertex.layer, 1.0),
@@ -151,5 +175,28 @@ fn fragment(vertex: VertexOutput,) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return select(vertex.color.rgba * object_color.r, object_color, vertex.is_color == 1u);
-}
\ No newline at end of file
+    return encode_output(select(vertex.color.rgba * object_color.r, object_color, vertex.is_color == 1u));
+}
+// Fragment shader that applies the coverage in linear space.
+// Light text on dark backgrounds gets its coverage boosted so it does not
+// look too thin when blended on sRGB surfaces.
+@fragment
+fn fragment_gamma(vertex: VertexOutput,) -> @location(0) vec4<f32> {
+    if vertex.is_color == 2u {
+        return encode_output(vertex.color);
+    }
+
+     let object_color = select(
+        textureSampleLevel(tex, tex_sample, vertex.uv.xy, vertex.layer, 1.0),
+        textureSampleLevel(emoji_tex, emoji_tex_sample, vertex.uv.xy, vertex.layer, 1.0),
+        vertex.is_color == 1u);
+
+    if select(object_color.r, object_color.a, vertex.is_color == 1u) <= 0.0 {
+        discard;
+    }
+
+    let luminance = dot(vertex.color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let coverage = pow(object_color.r, mix(1.0, 1.0 / 2.2, luminance));
+
+    return encode_output(select(vertex.color.rgba * coverage, object_color, vertex.is_color == 1u));
+}
@@ -6,10 +6,14 @@ mod instance_buffer;
 mod layout;
 mod pass;
 mod pipelines;
+mod readback;
 mod renderer;
+mod shader;
 mod static_vbo;
 mod system;
+mod variants;
 mod vbo;
+mod warm;
 
 pub use bounds::Bounds;
 pub use buffer::{
@@ -21,6 +25,7 @@ pub use instance_buffer::*;
 pub use layout::*;
 pub use pass::*;
 pub use pipelines::*;
+pub use readback::*;
 pub use renderer::*;
 pub use slotmap::KeyData;
 pub use static_vbo::*;
@@ -28,6 +33,19 @@ pub use system::*;
 pub use vbo::*;
 
 pub(crate) use ahash::{AHashMap, AHashSet, AHasher};
+pub(crate) use shader::load_shader;
+pub(crate) use variants::VariantRuns;
+
+/// Returns the [`cosmic_text::Color`] with its alpha multiplied by opacity.
+///
+pub(crate) fn apply_opacity(
+    color: cosmic_text::Color,
+    opacity: f32,
+) -> cosmic_text::Color {
+    let alpha = (color.a() as f32 * opacity.clamp(0.0, 1.0)).round() as u8;
+
+    cosmic_text::Color::rgba(color.r(), color.g(), color.b(), alpha)
+}
 
 pub(crate) type ABuildHasher = std::hash::BuildHasherDefault<AHasher>;
 pub(crate) type AIndexSet<K> = indexmap::IndexSet<K, ABuildHasher>;
@@ -31,6 +31,61 @@ impl Bounds {
         }
     }
 
+    /// Used to create [`Bounds`] from a position and size.
+    ///
+    /// # Arguments
+    /// - pos: Left and Bottom position of the [`Bounds`].
+    /// - size: Width and Height added to pos to get the Right and Top.
+    ///
+    pub fn from_pos_size(pos: Vec2, size: Vec2) -> Self {
+        Self {
+            left: pos.x,
+            bottom: pos.y,
+            right: pos.x + size.x,
+            top: pos.y + size.y,
+        }
+    }
+
+    /// Checks if a point is within the [`Bounds`].
+    /// Points on the Left and Bottom edges are inside, while points on the
+    /// Right and Top edges are outside.
+    ///
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        p.x >= self.left
+            && p.x < self.right
+            && p.y >= self.bottom
+            && p.y < self.top
+    }
+
+    /// Returns the overlapping area of both [`Bounds`].
+    /// Returns None if they do not overlap.
+    ///
+    pub fn intersect(&self, other: &Bounds) -> Option<Bounds> {
+        let bounds = Bounds {
+            left: self.left.max(other.left),
+            bottom: self.bottom.max(other.bottom),
+            right: self.right.min(other.right),
+            top: self.top.min(other.top),
+        };
+
+        if bounds.left < bounds.right && bounds.bottom < bounds.top {
+            Some(bounds)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest [`Bounds`] that contains both [`Bounds`].
+    ///
+    pub fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            left: self.left.min(other.left),
+            bottom: self.bottom.min(other.bottom),
+            right: self.right.max(other.right),
+            top: self.top.max(other.top),
+        }
+    }
+
     /// Used to update offset x and y within a limited range.
     ///
     /// # Arguments
@@ -21,6 +21,9 @@ pub struct BufferStore {
     /// if this does not match the current location internally we will resend
     /// the data to the gpu at the new location.
     pub index_pos: Range<usize>,
+    /// Byte Range within store that was partially changed. When set and the
+    /// store was not fully changed or moved only this Range is reuploaded.
+    pub dirty_range: Option<Range<usize>>,
 }
 
 impl BufferStore {
@@ -43,8 +46,31 @@ impl BufferStore {
             changed: false,
             store_pos: Range::default(),
             index_pos: Range::default(),
+            dirty_range: None,
         }
     }
+
+    /// Marks a byte Range of the store as changed so only that region gets
+    /// reuploaded to the gpu. Merges with any already dirty Range.
+    ///
+    /// # Arguments
+    /// - range: Byte Range within store that was changed.
+    ///
+    pub fn mark_dirty(&mut self, range: Range<usize>) {
+        // write_buffer requires 4 byte aligned offsets and sizes.
+        let align = wgpu::COPY_BUFFER_ALIGNMENT as usize;
+        let end = range.end.next_multiple_of(align).min(self.store.len());
+        let start = (range.start - range.start % align).min(end);
+
+        if start == end {
+            return;
+        }
+
+        self.dirty_range = Some(match self.dirty_range.take() {
+            Some(dirty) => dirty.start.min(start)..dirty.end.max(end),
+            None => start..end,
+        });
+    }
 }
 
 /// Pass of Data from a Vertex or Static Vertex used to Set the
@@ -1,17 +1,24 @@
 use crate::{GpuRenderer, GraphicsError};
 use async_trait::async_trait;
-use log::debug;
+use log::{debug, warn};
 use std::{path::Path, sync::Arc};
 use wgpu::{
     core::instance::RequestAdapterError, Adapter, Backend, Backends,
     DeviceType, Surface, TextureFormat,
 };
-use winit::{dpi::PhysicalSize, event::WindowEvent, window::Window};
+use winit::{
+    dpi::{LogicalSize, PhysicalSize},
+    event::WindowEvent,
+    window::Window,
+};
 
 /// Handles the [`wgpu::Device`] and [`wgpu::Queue`] returned from WGPU.
 pub struct GpuDevice {
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
+    /// Format of the Depth buffer and the pipelines depth stencil state.
+    /// Default is [`TextureFormat::Depth32Float`].
+    pub depth_format: TextureFormat,
 }
 
 impl GpuDevice {
@@ -22,6 +29,87 @@ impl GpuDevice {
     pub fn queue(&self) -> &wgpu::Queue {
         &self.queue
     }
+
+    /// Starts capturing validation errors so they are returned by
+    /// [`GpuDevice::pop_validation_scope`] instead of panicking in wgpu's
+    /// uncaptured error handler.
+    ///
+    pub fn push_validation_scope(&self) {
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+    }
+
+    /// Ends the scope started by [`GpuDevice::push_validation_scope`] and
+    /// returns the first validation error it captured.
+    /// On the web the scope can not be waited on so it always returns Ok.
+    ///
+    pub fn pop_validation_scope(&self) -> Result<(), wgpu::Error> {
+        let mut scope = std::pin::pin!(self.device.pop_error_scope());
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+
+        match std::future::Future::poll(scope.as_mut(), &mut cx) {
+            std::task::Poll::Ready(Some(error)) => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the Depth buffer [`TextureFormat`].
+    ///
+    pub fn depth_format(&self) -> TextureFormat {
+        self.depth_format
+    }
+
+    /// Returns an error if format is not a depth format or the
+    /// [`wgpu::Device`] lacks the features it needs, like
+    /// [`wgpu::Features::DEPTH32FLOAT_STENCIL8`].
+    ///
+    /// # Arguments
+    /// - format: Depth or Depth Stencil [`wgpu::TextureFormat`] to check.
+    ///
+    pub fn validate_depth_format(
+        &self,
+        format: TextureFormat,
+    ) -> Result<(), GraphicsError> {
+        if !format.is_depth_stencil_format() {
+            return Err(GraphicsError::InvalidDepthFormat(format));
+        }
+
+        let features = format.required_features();
+
+        if !self.device.features().contains(features) {
+            return Err(GraphicsError::MissingDepthFeatures {
+                format,
+                features,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns a [`wgpu::DepthStencilState`] using the Depth buffer format
+    /// and the given [`wgpu::StencilState`]. Use this for custom pipelines that
+    /// write or test a stencil mask. The built in pipelines all use
+    /// [`wgpu::StencilState::default`] so they never test or write the
+    /// stencil and are drawn the same with or without a mask.
+    ///
+    /// # Arguments
+    /// - stencil: Stencil state to use. Ignored if the format has no stencil.
+    ///
+    pub fn depth_stencil_state(
+        &self,
+        stencil: wgpu::StencilState,
+    ) -> wgpu::DepthStencilState {
+        wgpu::DepthStencilState {
+            format: self.depth_format,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: if self.depth_format.has_stencil_aspect() {
+                stencil
+            } else {
+                wgpu::StencilState::default()
+            },
+            bias: wgpu::DepthBiasState::default(),
+        }
+    }
 }
 
 /// Our own Adapter Power Settings.
@@ -49,6 +137,36 @@ pub struct AdapterOptions {
     pub compatible_surface: Option<Surface<'static>>,
 }
 
+/// Options used to Configure the [`wgpu::Surface`] when creating a
+/// [`GpuRenderer`].
+///
+#[derive(Copy, Clone, Debug)]
+pub struct SurfaceOptions {
+    /// How frames are presented to the screen. Default is AutoVsync.
+    pub present_mode: wgpu::PresentMode,
+    /// How the Surface is composited with the desktop. Validated against the
+    /// surface capabilities. Default is Auto.
+    pub alpha_mode: wgpu::CompositeAlphaMode,
+    /// How many frames may be queued ahead of presenting. 1 gives the lowest
+    /// input latency while 3 gives smoother throughput. Clamped between 1
+    /// and 3. Default is 2.
+    pub frame_latency: u32,
+    /// Depth buffer format the pipelines are created with. Validated against
+    /// the device features. Default is Depth32Float.
+    pub depth_format: wgpu::TextureFormat,
+}
+
+impl Default for SurfaceOptions {
+    fn default() -> Self {
+        Self {
+            present_mode: wgpu::PresentMode::AutoVsync,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            frame_latency: 2,
+            depth_format: wgpu::TextureFormat::Depth32Float,
+        }
+    }
+}
+
 /// Handles the [`wgpu::Adapter`], [`wgpu::Surface`], [`Window`].
 /// Also used to Keep track of [`wgpu::TextureFormat`], [`wgpu::SurfaceConfiguration`]
 /// and [`Window`] Sizes.
@@ -56,16 +174,24 @@ pub struct AdapterOptions {
 pub struct GpuWindow {
     /// GPU Adapter we will render from.
     pub(crate) adapter: wgpu::Adapter,
-    /// Window Surface we will Render Too.
-    pub(crate) surface: wgpu::Surface<'static>,
-    /// Window we are using to Render Too.
-    pub(crate) window: Arc<Window>,
+    /// Window Surface we will Render Too. None when headless.
+    pub(crate) surface: Option<wgpu::Surface<'static>>,
+    /// Window we are using to Render Too. None when headless.
+    pub(crate) window: Option<Arc<Window>>,
+    /// Texture rendered into instead of the Surface when headless.
+    pub(crate) headless_target: Option<wgpu::Texture>,
     /// Current Allowed surface_format of the GPU and Window.
+    /// This is the format frames are viewed and rendered as which can be the
+    /// sRGB view of a linear Surface.
     pub(crate) surface_format: wgpu::TextureFormat,
     /// Windows Overall Size.
     pub(crate) size: PhysicalSize<f32>,
     /// Windows Inner Size.
     pub(crate) inner_size: PhysicalSize<u32>,
+    /// Windows Scale Factor used to convert Physical pixels to Logical units.
+    pub(crate) scale_factor: f64,
+    /// If the Window is hidden behind other windows or minimized.
+    pub(crate) occluded: bool,
     /// Currently Accepted Surface configurations.
     pub(crate) surface_config: wgpu::SurfaceConfiguration,
 }
@@ -90,23 +216,201 @@ impl GpuWindow {
 
         self.surface_config.height = size.height;
         self.surface_config.width = size.width;
-        self.surface
-            .configure(gpu_device.device(), &self.surface_config);
+
+        if let Some(surface) = &self.surface {
+            surface.configure(gpu_device.device(), &self.surface_config);
+        } else {
+            self.headless_target = Some(Self::create_headless_target(
+                gpu_device,
+                self.surface_format,
+                size,
+            ));
+            self.inner_size = size;
+        }
+
         self.size = PhysicalSize::new(size.width as f32, size.height as f32);
 
         Ok(())
     }
 
+    /// Sets how many frames may be queued ahead of presenting and
+    /// reconfigures the [`wgpu::Surface`]. 1 gives the lowest input latency
+    /// while 3 gives smoother throughput. Clamped between 1 and 3.
+    /// Default is 2.
+    ///
+    pub fn set_frame_latency(&mut self, gpu_device: &GpuDevice, latency: u32) {
+        self.surface_config.desired_maximum_frame_latency = latency.clamp(1, 3);
+
+        if let Some(surface) = &self.surface {
+            if self.surface_config.width > 0 && self.surface_config.height > 0 {
+                surface.configure(gpu_device.device(), &self.surface_config);
+            }
+        }
+    }
+
+    /// Returns how many frames may be queued ahead of presenting.
+    ///
+    pub fn frame_latency(&self) -> u32 {
+        self.surface_config.desired_maximum_frame_latency
+    }
+
+    /// Returns the [`wgpu::CompositeAlphaMode`]'s the [`wgpu::Surface`]
+    /// supports. Empty when headless.
+    ///
+    pub fn supported_alpha_modes(&self) -> Vec<wgpu::CompositeAlphaMode> {
+        match &self.surface {
+            Some(surface) => {
+                surface.get_capabilities(&self.adapter).alpha_modes
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Sets how the [`wgpu::Surface`] is composited with the desktop and
+    /// reconfigures it. Use PreMultiplied or PostMultiplied with a
+    /// transparent [`Window`] for overlays. Returns
+    /// [`GraphicsError::UnsupportedAlphaMode`] if the surface does not
+    /// support the mode. Auto is always accepted.
+    ///
+    pub fn set_alpha_mode(
+        &mut self,
+        gpu_device: &GpuDevice,
+        alpha_mode: wgpu::CompositeAlphaMode,
+    ) -> Result<(), GraphicsError> {
+        let Some(surface) = &self.surface else {
+            self.surface_config.alpha_mode = alpha_mode;
+            return Ok(());
+        };
+
+        if alpha_mode != wgpu::CompositeAlphaMode::Auto
+            && !surface
+                .get_capabilities(&self.adapter)
+                .alpha_modes
+                .contains(&alpha_mode)
+        {
+            return Err(GraphicsError::UnsupportedAlphaMode(alpha_mode));
+        }
+
+        self.surface_config.alpha_mode = alpha_mode;
+
+        if self.surface_config.width > 0 && self.surface_config.height > 0 {
+            surface.configure(gpu_device.device(), &self.surface_config);
+        }
+
+        Ok(())
+    }
+
+    /// Returns how the [`wgpu::Surface`] is composited with the desktop.
+    ///
+    pub fn alpha_mode(&self) -> wgpu::CompositeAlphaMode {
+        self.surface_config.alpha_mode
+    }
+
     /// Returns the Size of the [`wgpu::Surface`].
     ///
     pub fn size(&self) -> PhysicalSize<f32> {
         self.size
     }
 
+    /// Returns the Scale Factor of the [`Window`].
+    /// This is updated upon [`WindowEvent::ScaleFactorChanged`].
+    ///
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Returns the Size of the [`wgpu::Surface`] in DPI independent Logical units.
+    ///
+    pub fn logical_size(&self) -> LogicalSize<f32> {
+        self.size.to_logical(self.scale_factor)
+    }
+
+    /// Returns true if rendering would do nothing because the Window is
+    /// occluded, minimized or has a zero size.
+    ///
+    pub fn is_rendering_paused(&self) -> bool {
+        self.occluded
+            || self
+                .window
+                .as_ref()
+                .and_then(|window| window.is_minimized())
+                .unwrap_or(false)
+            || self.size.width == 0.0
+            || self.size.height == 0.0
+            || self.inner_size.width == 0
+            || self.inner_size.height == 0
+    }
+
     /// Returns Reference to the [`wgpu::Surface`].
     ///
+    /// # Panics
+    /// If the [`GpuWindow`] is headless. Use [`GpuWindow::try_surface`] instead.
+    ///
     pub fn surface(&self) -> &wgpu::Surface {
-        &self.surface
+        self.try_surface()
+            .expect("A headless GpuWindow does not have a Surface.")
+    }
+
+    /// Returns Reference to the [`wgpu::Surface`] or None if headless.
+    ///
+    pub fn try_surface(&self) -> Option<&wgpu::Surface<'_>> {
+        self.surface.as_ref()
+    }
+
+    /// Returns true if there is no [`Window`] or [`wgpu::Surface`] and
+    /// rendering goes into a Texture instead.
+    ///
+    pub fn is_headless(&self) -> bool {
+        self.surface.is_none()
+    }
+
+    /// Returns the Texture rendered into when headless.
+    ///
+    pub fn headless_target(&self) -> Option<&wgpu::Texture> {
+        self.headless_target.as_ref()
+    }
+
+    /// Requests a redraw from the [`Window`] if there is one.
+    ///
+    fn request_redraw(&self) {
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    /// Updates the inner size from the [`Window`] if there is one.
+    ///
+    fn update_inner_size(&mut self) {
+        if let Some(window) = &self.window {
+            self.inner_size = window.inner_size();
+        }
+    }
+
+    /// Creates the Texture rendered into when headless.
+    ///
+    pub(crate) fn create_headless_target(
+        gpu_device: &GpuDevice,
+        format: TextureFormat,
+        size: PhysicalSize<u32>,
+    ) -> wgpu::Texture {
+        gpu_device
+            .device()
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("headless target texture"),
+                size: wgpu::Extent3d {
+                    width: size.width.max(1),
+                    height: size.height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[format],
+            })
     }
 
     /// Returns the [`wgpu::TextureFormat`].
@@ -125,7 +429,7 @@ impl GpuWindow {
         match event {
             WindowEvent::Resized(physical_size) => {
                 self.resize(gpu_device, *physical_size)?;
-                self.inner_size = self.window.inner_size();
+                self.update_inner_size();
 
                 if self.size.width == 0.0
                     || self.size.height == 0.0
@@ -135,7 +439,7 @@ impl GpuWindow {
                     return Ok(None);
                 }
 
-                self.window.request_redraw();
+                self.request_redraw();
             }
             WindowEvent::RedrawRequested => {
                 if self.size.width == 0.0
@@ -146,9 +450,13 @@ impl GpuWindow {
                     return Ok(None);
                 }
 
-                match self.surface.get_current_texture() {
+                let Some(surface) = &self.surface else {
+                    return Ok(None);
+                };
+
+                match surface.get_current_texture() {
                     Ok(frame) => {
-                        self.window.request_redraw();
+                        self.request_redraw();
                         return Ok(Some(frame));
                     }
                     Err(wgpu::SurfaceError::Lost) => {
@@ -157,7 +465,7 @@ impl GpuWindow {
                             self.size.height as u32,
                         );
                         self.resize(gpu_device, size)?;
-                        self.inner_size = self.window.inner_size();
+                        self.update_inner_size();
 
                         if self.size.width == 0.0
                             || self.size.height == 0.0
@@ -173,16 +481,24 @@ impl GpuWindow {
                     Err(e) => return Err(GraphicsError::from(e)),
                 }
 
-                self.window.request_redraw();
+                self.request_redraw();
             }
-            WindowEvent::Moved(_)
-            | WindowEvent::ScaleFactorChanged {
-                scale_factor: _,
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
                 inner_size_writer: _,
+            } => {
+                self.scale_factor = *scale_factor;
+                self.request_redraw();
+            }
+            WindowEvent::Occluded(occluded) => {
+                self.occluded = *occluded;
+
+                if !occluded {
+                    self.request_redraw();
+                }
             }
-            | WindowEvent::Focused(true)
-            | WindowEvent::Occluded(false) => {
-                self.window.request_redraw();
+            WindowEvent::Moved(_) | WindowEvent::Focused(true) => {
+                self.request_redraw();
             }
             _ => (),
         }
@@ -192,8 +508,18 @@ impl GpuWindow {
 
     /// Returns a Reference to [`Window`].
     ///
+    /// # Panics
+    /// If the [`GpuWindow`] is headless. Use [`GpuWindow::try_window`] instead.
+    ///
     pub fn window(&self) -> &Window {
-        &self.window
+        self.try_window()
+            .expect("A headless GpuWindow does not have a Window.")
+    }
+
+    /// Returns a Reference to [`Window`] or None if headless.
+    ///
+    pub fn try_window(&self) -> Option<&Window> {
+        self.window.as_deref()
     }
 
     /// Creates a Depth Texture from the [`GpuDevice`]
@@ -209,6 +535,14 @@ impl GpuWindow {
             depth_or_array_layers: 1,
         };
 
+        // Only some depth formats can be the destination of a copy.
+        let copy_dst = match gpu_device.depth_format {
+            TextureFormat::Depth32Float | TextureFormat::Depth16Unorm => {
+                wgpu::TextureUsages::COPY_DST
+            }
+            _ => wgpu::TextureUsages::empty(),
+        };
+
         let texture =
             gpu_device
                 .device()
@@ -218,11 +552,11 @@ impl GpuWindow {
                     mip_level_count: 1,
                     sample_count: 1,
                     dimension: wgpu::TextureDimension::D2,
-                    format: wgpu::TextureFormat::Depth32Float,
+                    format: gpu_device.depth_format,
                     usage: wgpu::TextureUsages::TEXTURE_BINDING
                         | wgpu::TextureUsages::RENDER_ATTACHMENT
-                        | wgpu::TextureUsages::COPY_DST,
-                    view_formats: &[TextureFormat::Depth32Float],
+                        | copy_dst,
+                    view_formats: &[gpu_device.depth_format],
                 });
 
         texture.create_view(&wgpu::TextureViewDescriptor::default())
@@ -241,7 +575,7 @@ pub trait AdapterExt {
         window: &Arc<Window>,
         device_descriptor: &wgpu::DeviceDescriptor,
         trace_path: Option<&Path>,
-        present_mode: wgpu::PresentMode,
+        surface_options: SurfaceOptions,
     ) -> Result<GpuRenderer, GraphicsError>;
 }
 
@@ -253,7 +587,7 @@ impl AdapterExt for wgpu::Adapter {
         window: &Arc<Window>,
         device_descriptor: &wgpu::DeviceDescriptor,
         trace_path: Option<&Path>,
-        present_mode: wgpu::PresentMode,
+        surface_options: SurfaceOptions,
     ) -> Result<GpuRenderer, GraphicsError> {
         let size = window.inner_size();
 
@@ -265,48 +599,88 @@ impl AdapterExt for wgpu::Adapter {
 
         debug!("{:?}", caps.formats);
 
-        let rgba = caps
-            .formats
-            .iter()
-            .position(|v| *v == TextureFormat::Rgba8UnormSrgb);
-        let bgra = caps
-            .formats
-            .iter()
-            .position(|v| *v == TextureFormat::Bgra8UnormSrgb);
-
-        let format = if let Some(pos) = rgba {
-            caps.formats[pos]
-        } else if let Some(pos) = bgra {
-            caps.formats[pos]
+        let find = |format: TextureFormat| {
+            caps.formats.iter().copied().find(|v| *v == format)
+        };
+
+        let surface_format = find(TextureFormat::Rgba8UnormSrgb)
+            .or_else(|| find(TextureFormat::Bgra8UnormSrgb))
+            .or_else(|| find(TextureFormat::Rgba8Unorm))
+            .or_else(|| find(TextureFormat::Bgra8Unorm))
+            .or_else(|| caps.formats.first().copied())
+            .ok_or(GraphicsError::NoSurfaceFormat)?;
+
+        // Linear surfaces get rendered through a sRGB view when supported,
+        // otherwise the shaders encode to sRGB themselves.
+        let srgb_view = surface_format.add_srgb_suffix();
+        let format = if srgb_view != surface_format
+            && self
+                .get_downlevel_capabilities()
+                .flags
+                .contains(wgpu::DownlevelFlags::SURFACE_VIEW_FORMATS)
+        {
+            srgb_view
+        } else {
+            surface_format
+        };
+
+        if !format.is_srgb() {
+            warn!("No sRGB surface format found. Shaders will encode to sRGB.");
+        }
+
+        debug!("surface format: {:?} view: {:?}", surface_format, format);
+        // Allows GpuRenderer::capture_frame_async to copy from the frame.
+        let usage = if caps.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
         } else {
-            panic!("Your Rendering Device does not support Bgra8UnormSrgb or Rgba8UnormSrgb");
+            wgpu::TextureUsages::RENDER_ATTACHMENT
         };
 
-        debug!("surface format: {:?}", format);
+        let alpha_mode = surface_options.alpha_mode;
+
+        if alpha_mode != wgpu::CompositeAlphaMode::Auto
+            && !caps.alpha_modes.contains(&alpha_mode)
+        {
+            return Err(GraphicsError::UnsupportedAlphaMode(alpha_mode));
+        }
+
         let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format,
+            usage,
+            format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode,
-            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            present_mode: surface_options.present_mode,
+            alpha_mode,
             view_formats: vec![format],
-            desired_maximum_frame_latency: 2,
+            desired_maximum_frame_latency: surface_options
+                .frame_latency
+                .clamp(1, 3),
+        };
+
+        let gpu_device = GpuDevice {
+            device,
+            queue,
+            depth_format: surface_options.depth_format,
         };
 
-        surface.configure(&device, &surface_config);
+        gpu_device.validate_depth_format(surface_options.depth_format)?;
+        surface.configure(&gpu_device.device, &surface_config);
         let inner_size = window.inner_size();
         let mut renderer = GpuRenderer::new(
             GpuWindow {
                 adapter: self,
-                surface,
-                window: window.clone(),
+                surface: Some(surface),
+                window: Some(window.clone()),
+                headless_target: None,
                 surface_format: format,
                 size: PhysicalSize::new(size.width as f32, size.height as f32),
                 surface_config,
                 inner_size,
+                scale_factor: window.scale_factor(),
+                occluded: false,
             },
-            GpuDevice { device, queue },
+            gpu_device,
         );
 
         // Creates the shader rendering pipelines for each renderer.
@@ -328,7 +702,7 @@ pub trait InstanceExt {
         options: AdapterOptions,
         device_descriptor: &wgpu::DeviceDescriptor,
         trace_path: Option<&Path>,
-        present_mode: wgpu::PresentMode,
+        surface_options: SurfaceOptions,
     ) -> Result<GpuRenderer, GraphicsError>;
 
     /// Gets a list of Avaliable Adapters based upon the [`AdapterOptions`].
@@ -383,7 +757,7 @@ impl InstanceExt for wgpu::Instance {
         options: AdapterOptions,
         device_descriptor: &wgpu::DeviceDescriptor,
         trace_path: Option<&Path>,
-        present_mode: wgpu::PresentMode,
+        surface_options: SurfaceOptions,
     ) -> Result<GpuRenderer, GraphicsError> {
         let mut adapters = self.get_adapters(options);
 
@@ -395,7 +769,7 @@ impl InstanceExt for wgpu::Instance {
                     &window,
                     device_descriptor,
                     trace_path,
-                    present_mode,
+                    surface_options,
                 )
                 .await;
 
@@ -13,7 +13,9 @@ pub type Index = AscendingKey;
 ///
 /// Positions are all calculated as (pos * 10000.0) as u32 to increase speed of sorting.
 /// Sort Order is order_layer -> alpha -> y reversed -> x -> z reversed.
-#[derive(Copy, Clone, PartialEq, Eq, Default)]
+/// Orders with a sort_key skip the position checks and sort by the key after
+/// all the orders without one in the same order_layer and alpha.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub struct DrawOrder {
     /// Layer to sort the buffer by. This is not the same as buffer_layer.
     /// Sorted by lowest to highest. First to Sort by.
@@ -30,6 +32,9 @@ pub struct DrawOrder {
     /// Z Position on the Screen. Sorted After X.
     /// Sorted by highest to lowest.
     pub z: u32,
+    /// Explicit key replacing the position when sorting.
+    /// Sorted by lowest to highest. Ties fall back to the position.
+    pub sort_key: Option<u64>,
 }
 
 impl PartialOrd for DrawOrder {
@@ -43,6 +48,7 @@ impl Ord for DrawOrder {
         self.order_layer
             .cmp(&other.order_layer)
             .then(self.alpha.cmp(&other.alpha))
+            .then(self.sort_key.cmp(&other.sort_key))
             .then(self.y.cmp(&other.y).reverse())
             .then(self.x.cmp(&other.x))
             .then(self.z.cmp(&other.z).reverse())
@@ -58,12 +64,22 @@ impl DrawOrder {
             x: (pos.x * 10000.0) as u32,
             y: (pos.y * 10000.0) as u32,
             z: (pos.z * 10000.0) as u32,
+            sort_key: None,
         }
     }
+
+    /// Sets the explicit sort_key used instead of the position.
+    pub fn with_sort_key(mut self, sort_key: Option<u64>) -> Self {
+        self.sort_key = sort_key;
+        self
+    }
 }
 
 /// OrderIndex Contains the information needed to Order the buffers and
 /// to set the buffers up for rendering.
+///
+/// Sorted by [`DrawOrder`] only. Buffers use a stable sort so equal Draw
+/// Orders keep the order they were added in.
 #[derive(Copy, Clone)]
 pub struct OrderedIndex {
     /// The Draw Order of the Buffer.
@@ -132,3 +148,37 @@ impl OrderedIndex {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slotmap::SlotMap;
+
+    #[test]
+    fn sort_keeps_submission_order_of_equal_draw_orders() {
+        let mut keys: SlotMap<Index, ()> = SlotMap::with_key();
+        let front = DrawOrder::new(false, &Vec3::new(0.0, 0.0, 1.0), 0);
+        let back = DrawOrder::new(false, &Vec3::new(0.0, 0.0, 1.0), 1);
+        // Inserted in reverse so submission order differs from key order.
+        let mut indexes: Vec<Index> = (0..6).map(|_| keys.insert(())).collect();
+        indexes.reverse();
+
+        let mut processing: Vec<OrderedIndex> = indexes
+            .iter()
+            .enumerate()
+            .map(|(i, index)| {
+                let order = if i % 2 == 0 { back } else { front };
+                OrderedIndex::new(order, *index, 0)
+            })
+            .collect();
+
+        processing.sort();
+
+        let sorted: Vec<Index> =
+            processing.iter().map(|ordered| ordered.index).collect();
+        let expected: Vec<Index> =
+            [1, 3, 5, 0, 2, 4].into_iter().map(|i| indexes[i]).collect();
+
+        assert_eq!(sorted, expected);
+    }
+}
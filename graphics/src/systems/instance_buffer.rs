@@ -1,6 +1,6 @@
 use crate::{
-    Bounds, Buffer, BufferLayout, CameraType, GpuDevice, GpuRenderer,
-    OrderedIndex,
+    Bounds, Buffer, BufferLayout, CameraType, DrawOrder, GpuDevice,
+    GpuRenderer, Index, OrderedIndex,
 };
 use std::ops::Range;
 
@@ -35,6 +35,9 @@ pub struct InstanceBuffer<K: BufferLayout> {
     needed_size: usize,
     /// Deturmines if we need to use clipped_buffers or Buffers for Rendering.
     is_clipped: bool,
+    /// Sorted (buffer_layer, [`Index`], [`DrawOrder`]) list from the last finalize.
+    /// Only gathered when enabled with set_debug_order.
+    debug_order: Option<Vec<(usize, Index, DrawOrder)>>,
 }
 
 impl<K: BufferLayout> InstanceBuffer<K> {
@@ -63,6 +66,7 @@ impl<K: BufferLayout> InstanceBuffer<K> {
             layer_size: layer_size.max(32),
             needed_size: 0,
             is_clipped: false,
+            debug_order: None,
         }
     }
 
@@ -103,6 +107,46 @@ impl<K: BufferLayout> InstanceBuffer<K> {
         }
     }
 
+    /// Adds many Buffers to the unprocessed list at once. The layers are
+    /// grown once up front which is faster than many
+    /// [`InstanceBuffer::add_buffer_store`] calls when adding lots of Objects.
+    ///
+    /// # Arguments
+    /// - indices: The Order Index and Buffer Layer of each Object to render.
+    ///
+    pub fn add_buffer_stores(
+        &mut self,
+        renderer: &GpuRenderer,
+        indices: &[(OrderedIndex, usize)],
+    ) {
+        let Some(max_layer) = indices.iter().map(|(_, layer)| *layer).max()
+        else {
+            return;
+        };
+
+        let offset = max_layer.saturating_add(1);
+
+        if self.unprocessed.len() < offset {
+            for i in self.unprocessed.len()..offset {
+                //Like add_buffer_store only layers getting data start sized.
+                let used = indices.iter().any(|(_, layer)| *layer == i);
+
+                self.unprocessed.push(if used {
+                    Vec::with_capacity(self.layer_size)
+                } else {
+                    Vec::new()
+                });
+            }
+        }
+
+        for (index, buffer_layer) in indices {
+            if let Some(store) = renderer.get_buffer(index.index) {
+                self.needed_size += store.store.len();
+                self.unprocessed[*buffer_layer].push(*index);
+            }
+        }
+    }
+
     fn buffer_write(
         &self,
         renderer: &mut GpuRenderer,
@@ -112,6 +156,7 @@ impl<K: BufferLayout> InstanceBuffer<K> {
         changed: bool,
     ) {
         let mut write_buffer = false;
+        let mut dirty_range = None;
         let old_pos = *pos as u64;
 
         if let Some(store) = renderer.get_buffer_mut(buf.index) {
@@ -123,13 +168,20 @@ impl<K: BufferLayout> InstanceBuffer<K> {
                 write_buffer = true
             }
 
+            dirty_range = store.dirty_range.take();
             *pos += store.store.len();
             *count += (store.store.len() / K::stride()) as u32;
         }
 
-        if write_buffer {
-            if let Some(store) = renderer.get_buffer(buf.index) {
+        if let Some(store) = renderer.get_buffer(buf.index) {
+            if write_buffer {
                 self.buffer.write(&renderer.device, &store.store, old_pos);
+            } else if let Some(dirty) = dirty_range {
+                self.buffer.write(
+                    &renderer.device,
+                    &store.store[dirty.clone()],
+                    old_pos + dirty.start as u64,
+                );
             }
         }
     }
@@ -210,6 +262,16 @@ impl<K: BufferLayout> InstanceBuffer<K> {
 
         self.needed_size = 0;
 
+        if let Some(debug_order) = &mut self.debug_order {
+            debug_order.clear();
+
+            for (layer, processing) in self.unprocessed.iter().enumerate() {
+                debug_order.extend(
+                    processing.iter().map(|buf| (layer, buf.index, buf.order)),
+                );
+            }
+        }
+
         for buffer in &mut self.unprocessed {
             buffer.clear()
         }
@@ -267,6 +329,53 @@ impl<K: BufferLayout> InstanceBuffer<K> {
         self.is_clipped
     }
 
+    /// Returns the Buffer Layer's that have something to render after
+    /// [`InstanceBuffer::finalize`] from lowest to highest.
+    ///
+    pub fn occupied_layers(&self) -> impl Iterator<Item = usize> + '_ {
+        let clipped = self
+            .clipped_buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| self.is_clipped && !buffer.is_empty())
+            .map(|(layer, _)| layer);
+        let buffers = self
+            .buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, details)| {
+                !self.is_clipped
+                    && details
+                        .as_ref()
+                        .is_some_and(|details| details.end > details.start)
+            })
+            .map(|(layer, _)| layer);
+
+        clipped.chain(buffers)
+    }
+
+    /// Returns one past the highest Buffer Layer with something to render
+    /// after [`InstanceBuffer::finalize`] or 0 if there is nothing.
+    ///
+    pub fn layer_count(&self) -> usize {
+        self.occupied_layers().last().map_or(0, |layer| layer + 1)
+    }
+
+    /// Enables or Disables gathering the sorted render order during finalize.
+    /// Used for debugging why an Object renders above or below another.
+    ///
+    pub fn set_debug_order(&mut self, enable: bool) {
+        self.debug_order = if enable { Some(Vec::new()) } else { None };
+    }
+
+    /// Returns the sorted (buffer_layer, [`Index`], [`DrawOrder`]) list from the last
+    /// [`InstanceBuffer::finalize`] in the order they were rendered.
+    /// Is empty unless enabled with [`InstanceBuffer::set_debug_order`].
+    ///
+    pub fn debug_order(&self) -> &[(usize, Index, DrawOrder)] {
+        self.debug_order.as_deref().unwrap_or(&[])
+    }
+
     /// Sets the Buffer into Clipping mode.
     /// This will Produce a clipped_buffers instead of the buffers which
     /// will still be layered but a Vector of individual objects will Exist rather
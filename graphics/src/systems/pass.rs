@@ -1,3 +1,5 @@
+use crate::{GpuRenderer, GraphicsError};
+
 /// The `Pass` trait represents either a render or compute pass. This way the passes can be
 /// implemented in a modular way.
 pub trait Pass {
@@ -10,3 +12,152 @@ pub trait Pass {
         encoder: &mut wgpu::CommandEncoder,
     );
 }
+
+/// Builds a [`wgpu::RenderPass`] over the [`crate::GpuRenderer`]'s frame
+/// buffer and depth buffer so each pass picks if it clears or keeps what
+/// earlier passes drew. Defaults to loading both so a later pass draws on
+/// top of the frame while still depth testing against it.
+///
+/// Clear the depth buffer for a UI pass drawn after the world so it ignores
+/// the world's depth.
+///
+#[derive(Clone, Debug)]
+pub struct RenderPassBuilder<'a> {
+    label: Option<&'a str>,
+    color_load: wgpu::LoadOp<wgpu::Color>,
+    depth_load: Option<wgpu::LoadOp<f32>>,
+    stencil_load: Option<wgpu::LoadOp<u32>>,
+}
+
+impl Default for RenderPassBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            label: Some("Render Pass"),
+            color_load: wgpu::LoadOp::Load,
+            depth_load: Some(wgpu::LoadOp::Load),
+            stencil_load: None,
+        }
+    }
+}
+
+impl<'a> RenderPassBuilder<'a> {
+    /// Creates a new [`RenderPassBuilder`] that loads the color and depth.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the label shown in graphics debuggers.
+    ///
+    pub fn with_label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Clears the frame buffer to color before drawing.
+    ///
+    pub fn with_color_clear(mut self, color: wgpu::Color) -> Self {
+        self.color_load = wgpu::LoadOp::Clear(color);
+        self
+    }
+
+    /// Keeps what earlier passes drew into the frame buffer.
+    ///
+    pub fn with_color_load(mut self) -> Self {
+        self.color_load = wgpu::LoadOp::Load;
+        self
+    }
+
+    /// Clears the depth buffer to depth before drawing. 1.0 is the farthest.
+    ///
+    pub fn with_depth_clear(mut self, depth: f32) -> Self {
+        self.depth_load = Some(wgpu::LoadOp::Clear(depth));
+        self
+    }
+
+    /// Keeps the depth earlier passes wrote so objects depth test against it.
+    ///
+    pub fn with_depth_load(mut self) -> Self {
+        self.depth_load = Some(wgpu::LoadOp::Load);
+        self
+    }
+
+    /// Leaves the depth buffer out of the pass. Only pipelines made without
+    /// a depth stencil state can be used in it.
+    ///
+    pub fn without_depth(mut self) -> Self {
+        self.depth_load = None;
+        self
+    }
+
+    /// Clears the stencil to value before drawing.
+    /// Ignored when the depth format has no Stencil aspect.
+    ///
+    pub fn with_stencil_clear(mut self, value: u32) -> Self {
+        self.stencil_load = Some(wgpu::LoadOp::Clear(value));
+        self
+    }
+
+    /// Keeps the stencil earlier passes wrote.
+    /// Ignored when the depth format has no Stencil aspect.
+    ///
+    pub fn with_stencil_load(mut self) -> Self {
+        self.stencil_load = Some(wgpu::LoadOp::Load);
+        self
+    }
+
+    /// Begins the [`wgpu::RenderPass`] on the current frame buffer.
+    /// Returns [`GraphicsError::FrameNotAcquired`] if there is no frame.
+    ///
+    pub fn begin<'e>(
+        &self,
+        renderer: &GpuRenderer,
+        encoder: &'e mut wgpu::CommandEncoder,
+    ) -> Result<wgpu::RenderPass<'e>, GraphicsError> {
+        let view = renderer
+            .frame_buffer()
+            .as_ref()
+            .ok_or(GraphicsError::FrameNotAcquired)?;
+        let depth_stencil_attachment = self
+            .depth_load
+            .map(|load| {
+                renderer.depth_stencil_attachment(
+                    Some(wgpu::Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    self.stencil_load.map(|load| wgpu::Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                )
+            })
+            .or_else(|| {
+                // Stencil only passes still need the depth attachment.
+                self.stencil_load.map(|load| {
+                    renderer.depth_stencil_attachment(
+                        None,
+                        Some(wgpu::Operations {
+                            load,
+                            store: wgpu::StoreOp::Store,
+                        }),
+                    )
+                })
+            });
+
+        Ok(encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: self.label,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: self.color_load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        }))
+    }
+}
@@ -57,6 +57,46 @@ impl PipelineStorage {
         );
     }
 
+    /// Creates a new [`wgpu::RenderPipeline`] from [`PipeLineLayout`] within a
+    /// validation error scope. If wgpu reports an error the previous
+    /// [`wgpu::RenderPipeline`] is kept and the error is returned.
+    ///
+    pub fn try_create_pipeline<K: PipeLineLayout>(
+        &mut self,
+        device: &mut GpuDevice,
+        layout_storage: &mut LayoutStorage,
+        surface_format: wgpu::TextureFormat,
+        pipeline: K,
+    ) -> Result<(), wgpu::Error> {
+        device.push_validation_scope();
+        let render_pipeline =
+            pipeline.create_layout(device, layout_storage, surface_format);
+        device.pop_validation_scope()?;
+
+        self.map.insert(pipeline.layout_key(), render_pipeline);
+        Ok(())
+    }
+
+    /// Inserts an already created [`wgpu::RenderPipeline`] into the internal map using
+    /// the [`PipeLineLayout`]'s key. Replaces any existing [`wgpu::RenderPipeline`].
+    ///
+    pub fn insert_pipeline<K: PipeLineLayout>(
+        &mut self,
+        pipeline: K,
+        render_pipeline: wgpu::RenderPipeline,
+    ) {
+        self.map.insert(pipeline.layout_key(), render_pipeline);
+    }
+
+    /// Removes the [`wgpu::RenderPipeline`] of the [`PipeLineLayout`]'s key.
+    ///
+    pub fn remove_pipeline<K: PipeLineLayout>(
+        &mut self,
+        pipeline: K,
+    ) -> Option<wgpu::RenderPipeline> {
+        self.map.remove(&pipeline.layout_key())
+    }
+
     /// Retrieves a Reference to a [`wgpu::RenderPipeline`] within the internal map for rendering.
     ///
     pub fn get_pipeline<K: PipeLineLayout>(
@@ -0,0 +1,203 @@
+use crate::{GpuRenderer, GraphicsError, OtherError};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// Pixels read back from the current frame with
+/// [`GpuRenderer::capture_frame_async`].
+///
+#[derive(Clone, Debug)]
+pub struct FrameCapture {
+    /// Width of the frame in pixels.
+    pub width: u32,
+    /// Height of the frame in pixels.
+    pub height: u32,
+    /// Format of the Surface the bytes were copied from.
+    pub format: wgpu::TextureFormat,
+    /// Tightly packed rows of bytes in the Surface format.
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Default)]
+struct ReadbackState {
+    result: Option<Result<(), wgpu::BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+/// Future returned by [`GpuRenderer::capture_frame_async`].
+/// Resolves once the GPU has finished mapping the frame copy.
+/// On native targets polling also polls the [`wgpu::Device`] without
+/// blocking and wakes itself until the mapping is done. On the web the
+/// browser drives the mapping callback instead.
+///
+pub struct FrameReadback {
+    device: wgpu::Device,
+    buffer: wgpu::Buffer,
+    state: Arc<Mutex<ReadbackState>>,
+    width: u32,
+    height: u32,
+    row_size: u32,
+    padded_row_size: u32,
+    format: wgpu::TextureFormat,
+}
+
+impl Future for FrameReadback {
+    type Output = Result<FrameCapture, GraphicsError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = self.device.poll(wgpu::Maintain::Poll);
+
+        let result = {
+            let mut state = match self.state.lock() {
+                Ok(state) => state,
+                Err(_) => {
+                    return Poll::Ready(Err(OtherError::new(
+                        "Frame readback state was poisoned.",
+                    )
+                    .into()));
+                }
+            };
+
+            match state.result.take() {
+                Some(result) => result,
+                None => {
+                    state.waker = Some(cx.waker().clone());
+
+                    // Nothing else polls the device so ask to be polled again.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    cx.waker().wake_by_ref();
+
+                    return Poll::Pending;
+                }
+            }
+        };
+
+        if result.is_err() {
+            return Poll::Ready(Err(OtherError::new(
+                "Failed to map the Frame readback.",
+            )
+            .into()));
+        }
+
+        Poll::Ready(Ok(self.read_mapped()))
+    }
+}
+
+impl FrameReadback {
+    fn read_mapped(&self) -> FrameCapture {
+        let bytes = {
+            let mapped = self.buffer.slice(..).get_mapped_range();
+
+            mapped
+                .chunks(self.padded_row_size as usize)
+                .flat_map(|row| &row[..self.row_size as usize])
+                .copied()
+                .collect()
+        };
+
+        self.buffer.unmap();
+
+        FrameCapture {
+            width: self.width,
+            height: self.height,
+            format: self.format,
+            bytes,
+        }
+    }
+}
+
+impl GpuRenderer {
+    /// Copies the current frame into a readback buffer and returns a
+    /// [`FrameReadback`] Future that resolves to the [`FrameCapture`].
+    /// Must be called after [`GpuRenderer::update`] and rendering but before
+    /// [`GpuRenderer::present`]. Works on both native and wasm32 targets and
+    /// with a headless GpuRenderer.
+    ///
+    pub fn capture_frame_async(&self) -> Result<FrameReadback, GraphicsError> {
+        let texture = self
+            .frame_texture()
+            .ok_or(GraphicsError::FrameNotAcquired)?;
+
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(OtherError::new(
+                "The Surface does not support Copying from its frames.",
+            )
+            .into());
+        }
+
+        let format = texture.format();
+        let width = texture.width();
+        let height = texture.height();
+        let row_size = width * format.block_copy_size(None).unwrap_or(4);
+        let padded_row_size = row_size
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = self.device().create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Frame readback buffer"),
+            size: padded_row_size as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("Frame readback encoder"),
+            },
+        );
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.queue().submit(std::iter::once(encoder.finish()));
+
+        let state = Arc::new(Mutex::new(ReadbackState::default()));
+        let callback_state = Arc::clone(&state);
+
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                if let Ok(mut state) = callback_state.lock() {
+                    state.result = Some(result);
+
+                    if let Some(waker) = state.waker.take() {
+                        waker.wake();
+                    }
+                }
+            });
+
+        Ok(FrameReadback {
+            device: self.device().clone(),
+            buffer,
+            state,
+            width,
+            height,
+            row_size,
+            padded_row_size,
+            format,
+        })
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{
-    BufferPass, BufferStore, GpuDevice, GpuWindow, GraphicsError, Index,
-    Layout, LayoutStorage, OtherError, PipeLineLayout, PipelineStorage,
-    StaticVertexBuffer,
+    AHashMap, AdapterOptions, BufferPass, BufferStore, GpuDevice, GpuWindow,
+    GraphicsError, Index, InstanceExt, Layout, LayoutStorage, PipeLineLayout,
+    PipelineStorage, StaticVertexBuffer,
 };
 use cosmic_text::FontSystem;
 use slotmap::SlotMap;
 use std::rc::Rc;
 
-use winit::{dpi::PhysicalSize, event::WindowEvent, window::Window};
+use winit::{
+    dpi::{LogicalSize, PhysicalSize},
+    event::WindowEvent,
+    window::{CursorIcon, Icon, Window},
+};
 
 /// Handles the [`GpuWindow`], [`GpuDevice`] and [`BufferStore`]'s.
 /// Also handles other information important to Rendering to the GPU.
@@ -18,6 +22,10 @@ pub struct GpuRenderer {
     pub(crate) buffer_stores: SlotMap<Index, BufferStore>,
     pub(crate) layout_storage: LayoutStorage,
     pub(crate) pipeline_storage: PipelineStorage,
+    /// Fragment source and entry point of each Image pipeline variant.
+    pub(crate) image_variant_sources: AHashMap<u32, (String, String)>,
+    /// Fragment source and entry point of each Rect pipeline variant.
+    pub(crate) rect_variant_sources: AHashMap<u32, (String, String)>,
     pub(crate) depthbuffer: wgpu::TextureView,
     pub(crate) framebuffer: Option<wgpu::TextureView>,
     pub(crate) frame: Option<wgpu::SurfaceTexture>,
@@ -63,6 +71,8 @@ impl GpuRenderer {
             buffer_stores: SlotMap::with_capacity_and_key(1024),
             layout_storage: LayoutStorage::new(),
             pipeline_storage: PipelineStorage::new(),
+            image_variant_sources: AHashMap::default(),
+            rect_variant_sources: AHashMap::default(),
             depthbuffer: depth_buffer,
             framebuffer: None,
             frame: None,
@@ -72,6 +82,95 @@ impl GpuRenderer {
         }
     }
 
+    /// Creates a New headless GpuRenderer that renders into a Texture
+    /// instead of a [`Window`]'s [`wgpu::Surface`]. Useful for tests and CI
+    /// with a software adapter. [`GpuRenderer::update`] sets the frame buffer
+    /// to the Texture on [`WindowEvent::RedrawRequested`].
+    ///
+    /// # Arguments
+    /// - instance: [`wgpu::Instance`] to get the Adapters from.
+    /// - options: [`AdapterOptions`] used to pick the Adapter.
+    /// - device_descriptor: Used to request the [`wgpu::Device`].
+    /// - size: Size of the Texture rendered into.
+    ///
+    pub async fn new_headless(
+        instance: &wgpu::Instance,
+        options: AdapterOptions,
+        device_descriptor: &wgpu::DeviceDescriptor<'_>,
+        size: PhysicalSize<u32>,
+    ) -> Result<Self, GraphicsError> {
+        let mut adapters = instance.get_adapters(options);
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+        while let Some((adapter, _)) = adapters.pop() {
+            let Ok((device, queue)) =
+                adapter.request_device(device_descriptor, None).await
+            else {
+                continue;
+            };
+
+            let device = GpuDevice {
+                device,
+                queue,
+                depth_format: wgpu::TextureFormat::Depth32Float,
+            };
+            let headless_target =
+                GpuWindow::create_headless_target(&device, format, size);
+            let window = GpuWindow {
+                adapter,
+                surface: None,
+                window: None,
+                headless_target: Some(headless_target),
+                surface_format: format,
+                size: PhysicalSize::new(size.width as f32, size.height as f32),
+                inner_size: size,
+                scale_factor: 1.0,
+                occluded: false,
+                surface_config: wgpu::SurfaceConfiguration {
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    format,
+                    width: size.width,
+                    height: size.height,
+                    present_mode: wgpu::PresentMode::Fifo,
+                    alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                    view_formats: vec![format],
+                    desired_maximum_frame_latency: 2,
+                },
+            };
+
+            let mut renderer = GpuRenderer::new(window, device);
+            renderer.create_pipelines(format);
+            return Ok(renderer);
+        }
+
+        Err(GraphicsError::Adapter(
+            wgpu::core::instance::RequestAdapterError::NotFound,
+        ))
+    }
+
+    /// Returns true if the GpuRenderer was made with
+    /// [`GpuRenderer::new_headless`].
+    ///
+    pub fn is_headless(&self) -> bool {
+        self.window.is_headless()
+    }
+
+    /// Returns the Texture rendered into when headless.
+    ///
+    pub fn headless_target(&self) -> Option<&wgpu::Texture> {
+        self.window.headless_target()
+    }
+
+    /// Returns the Texture of the current frame or the headless Texture.
+    ///
+    pub(crate) fn frame_texture(&self) -> Option<&wgpu::Texture> {
+        match &self.frame {
+            Some(frame) => Some(&frame.texture),
+            None if self.framebuffer.is_some() => self.headless_target(),
+            None => None,
+        }
+    }
+
     /// Returns a reference to [`wgpu::Adapter`].
     ///
     pub fn adapter(&self) -> &wgpu::Adapter {
@@ -87,6 +186,80 @@ impl GpuRenderer {
         self.window.resize(&self.device, size)
     }
 
+    /// Sets how many frames may be queued ahead of presenting.
+    /// 1 gives the lowest input latency while 3 gives smoother throughput.
+    /// Clamped between 1 and 3. Default is 2.
+    ///
+    pub fn set_frame_latency(&mut self, latency: u32) {
+        self.window.set_frame_latency(&self.device, latency)
+    }
+
+    /// Returns how many frames may be queued ahead of presenting.
+    ///
+    pub fn frame_latency(&self) -> u32 {
+        self.window.frame_latency()
+    }
+
+    /// Returns the [`wgpu::CompositeAlphaMode`]'s the Surface supports.
+    /// Empty when headless.
+    ///
+    pub fn supported_alpha_modes(&self) -> Vec<wgpu::CompositeAlphaMode> {
+        self.window.supported_alpha_modes()
+    }
+
+    /// Sets how the Surface is composited with the desktop.
+    /// Use PreMultiplied or PostMultiplied with a transparent [`Window`]
+    /// for overlays. Errors if the Surface does not support the mode.
+    ///
+    pub fn set_alpha_mode(
+        &mut self,
+        alpha_mode: wgpu::CompositeAlphaMode,
+    ) -> Result<(), GraphicsError> {
+        self.window.set_alpha_mode(&self.device, alpha_mode)
+    }
+
+    /// Returns how the Surface is composited with the desktop.
+    ///
+    pub fn alpha_mode(&self) -> wgpu::CompositeAlphaMode {
+        self.window.alpha_mode()
+    }
+
+    /// Sets the [`Window`]'s icon from an RGBA image.
+    /// Does nothing when headless.
+    ///
+    pub fn set_window_icon(
+        &self,
+        img: &image::RgbaImage,
+    ) -> Result<(), GraphicsError> {
+        if let Some(window) = self.window.try_window() {
+            let icon = Icon::from_rgba(
+                img.as_raw().clone(),
+                img.width(),
+                img.height(),
+            )?;
+            window.set_window_icon(Some(icon));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the [`Window`]'s cursor shape. Does nothing when headless.
+    ///
+    pub fn set_cursor(&self, cursor: CursorIcon) {
+        if let Some(window) = self.window.try_window() {
+            window.set_cursor(cursor);
+        }
+    }
+
+    /// Shows or hides the cursor over the [`Window`].
+    /// Does nothing when headless.
+    ///
+    pub fn set_cursor_visible(&self, visible: bool) {
+        if let Some(window) = self.window.try_window() {
+            window.set_cursor_visible(visible);
+        }
+    }
+
     /// Returns a reference to the Optional [`wgpu::TextureView`]: frame buffer.
     ///
     pub fn frame_buffer(&self) -> &Option<wgpu::TextureView> {
@@ -111,10 +284,47 @@ impl GpuRenderer {
         self.window.inner_size
     }
 
+    /// Returns the windows Scale Factor.
+    /// Multiply Logical units by this to get Physical pixels.
+    ///
+    pub fn scale_factor(&self) -> f64 {
+        self.window.scale_factor
+    }
+
+    /// Returns true if the window is occluded, minimized or has a zero size.
+    /// Use this to skip simulation and rendering work while in the background.
+    ///
+    pub fn is_rendering_paused(&self) -> bool {
+        self.window.is_rendering_paused()
+    }
+
+    /// Returns the windows [`LogicalSize`].
+    /// Useful to Layout UI in DPI independent units.
+    ///
+    pub fn logical_size(&self) -> LogicalSize<f32> {
+        self.window.logical_size()
+    }
+
     /// Returns a reference to [`wgpu::Surface`].
     ///
+    /// # Panics
+    /// If the GpuRenderer is headless. Use [`GpuRenderer::try_surface`] instead.
+    ///
     pub fn surface(&self) -> &wgpu::Surface {
-        &self.window.surface
+        self.window.surface()
+    }
+
+    /// Returns a reference to [`wgpu::Surface`] or None if headless.
+    ///
+    pub fn try_surface(&self) -> Option<&wgpu::Surface<'_>> {
+        self.window.try_surface()
+    }
+
+    /// Returns true if the render format is linear so the shaders need to
+    /// encode their output to sRGB.
+    ///
+    pub fn needs_srgb_encode(&self) -> bool {
+        !self.window.surface_format.is_srgb()
     }
 
     /// Returns the surfaces [`wgpu::TextureFormat`].
@@ -131,16 +341,30 @@ impl GpuRenderer {
         &mut self,
         event: &WindowEvent,
     ) -> Result<bool, GraphicsError> {
+        if self.window.is_headless() {
+            self.window.update(&self.device, event)?;
+
+            if !matches!(event, WindowEvent::RedrawRequested) {
+                return Ok(false);
+            }
+
+            self.framebuffer = self.window.headless_target().map(|texture| {
+                texture.create_view(&wgpu::TextureViewDescriptor::default())
+            });
+
+            return Ok(self.framebuffer.is_some());
+        }
+
         let frame = match self.window.update(&self.device, event)? {
             Some(frame) => frame,
             _ => return Ok(false),
         };
 
-        self.framebuffer = Some(
-            frame
-                .texture
-                .create_view(&wgpu::TextureViewDescriptor::default()),
-        );
+        self.framebuffer =
+            Some(frame.texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(self.window.surface_format),
+                ..Default::default()
+            }));
         self.frame = Some(frame);
 
         Ok(true)
@@ -148,8 +372,17 @@ impl GpuRenderer {
 
     /// Returns a reference to [`Window`].
     ///
+    /// # Panics
+    /// If the GpuRenderer is headless. Use [`GpuRenderer::try_window`] instead.
+    ///
     pub fn window(&self) -> &Window {
-        &self.window.window
+        self.window.window()
+    }
+
+    /// Returns a reference to [`Window`] or None if headless.
+    ///
+    pub fn try_window(&self) -> Option<&Window> {
+        self.window.try_window()
     }
 
     /// Updates the Internally Stored Depth Buffer.
@@ -158,10 +391,75 @@ impl GpuRenderer {
         self.depthbuffer = self.window.create_depth_texture(&self.device);
     }
 
+    /// Returns the Depth buffer [`wgpu::TextureFormat`].
+    ///
+    pub fn depth_format(&self) -> wgpu::TextureFormat {
+        self.device.depth_format
+    }
+
+    /// Returns true if the Depth buffer format contains a Stencil aspect.
+    ///
+    pub fn has_stencil(&self) -> bool {
+        self.device.depth_format.has_stencil_aspect()
+    }
+
+    /// Sets the Depth buffer format, for example [`wgpu::TextureFormat::Depth24PlusStencil8`]
+    /// to enable Stencil masks. Recreates the Depth buffer, the built in
+    /// pipelines and the pipeline variants. Variants that fail to rebuild are
+    /// logged and removed so they render with the default pipeline.
+    /// Custom pipelines must be recreated after calling this. The built in
+    /// pipelines keep [`wgpu::StencilState::default`] so only custom
+    /// pipelines made with [`GpuDevice::depth_stencil_state`] use the stencil.
+    /// Returns an error if the format is not a depth format or the device
+    /// lacks its features, leaving the current format in place.
+    ///
+    /// # Arguments
+    /// - format: Depth or Depth Stencil [`wgpu::TextureFormat`].
+    ///
+    pub fn set_depth_format(
+        &mut self,
+        format: wgpu::TextureFormat,
+    ) -> Result<(), GraphicsError> {
+        self.device.validate_depth_format(format)?;
+
+        if self.device.depth_format != format {
+            self.device.depth_format = format;
+            self.update_depth_texture();
+            self.create_pipelines(self.surface_format());
+            self.rebuild_pipeline_variants(true);
+        }
+
+        Ok(())
+    }
+
+    /// Creates a [`wgpu::RenderPassDepthStencilAttachment`] for the Depth buffer.
+    /// stencil_ops is ignored when the Depth format has no Stencil aspect.
+    ///
+    /// # Arguments
+    /// - depth_ops: Depth buffer load and store operations.
+    /// - stencil_ops: Stencil load and store operations.
+    ///
+    pub fn depth_stencil_attachment(
+        &self,
+        depth_ops: Option<wgpu::Operations<f32>>,
+        stencil_ops: Option<wgpu::Operations<u32>>,
+    ) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+        wgpu::RenderPassDepthStencilAttachment {
+            view: &self.depthbuffer,
+            depth_ops,
+            stencil_ops: stencil_ops.filter(|_| self.has_stencil()),
+        }
+    }
+
     /// Presents the Current frame Buffer to the Window if Some().
     /// If the frame buffer does not Exist will return a Error.
     ///
     pub fn present(&mut self) -> Result<(), GraphicsError> {
+        // Headless frames are already in the Texture once submitted.
+        if self.window.is_headless() && self.framebuffer.take().is_some() {
+            return Ok(());
+        }
+
         self.framebuffer = None;
 
         match self.frame.take() {
@@ -169,9 +467,7 @@ impl GpuRenderer {
                 frame.present();
                 Ok(())
             }
-            None => Err(GraphicsError::Other(OtherError::new(
-                "Frame does not Exist. Did you forget to update the renderer?",
-            ))),
+            None => Err(GraphicsError::FrameNotAcquired),
         }
     }
 
@@ -217,6 +513,25 @@ impl GpuRenderer {
             .insert(BufferStore::new(store_size, index_size))
     }
 
+    /// Creates count New [`BufferStore`]'s for Rendering Object Data Storage
+    /// in one go and Returns their [`Index`]'s for Referencing them.
+    ///
+    pub fn new_buffers(
+        &mut self,
+        count: usize,
+        store_size: usize,
+        index_size: usize,
+    ) -> Vec<Index> {
+        self.buffer_stores.reserve(count);
+
+        (0..count)
+            .map(|_| {
+                self.buffer_stores
+                    .insert(BufferStore::new(store_size, index_size))
+            })
+            .collect()
+    }
+
     /// Creates a New [`BufferStore`] with default sizes for Rendering Object Data Storage and
     /// Returns its [`Index`] for Referencing it.
     ///
@@ -260,6 +575,51 @@ impl GpuRenderer {
         self.layout_storage.get_layout(layout)
     }
 
+    /// Recreates the built in pipelines from the shader files on disk.
+    /// Shaders that fail to compile are logged and fall back to their
+    /// embedded source. Pipelines that fail validation against their layout,
+    /// vertex attributes or bind groups are logged and keep the previous
+    /// pipeline. Pipeline variants are rebuilt with the reloaded vertex
+    /// shaders and keep their previous pipeline if that fails.
+    /// Custom pipelines are not recreated.
+    ///
+    #[cfg(feature = "shader_hot_reload")]
+    pub fn reload_pipelines(&mut self) {
+        let surface_format = self.surface_format();
+
+        self.reload_pipeline(surface_format, crate::ImageRenderPipeline);
+        self.reload_pipeline(surface_format, crate::MapRenderPipeline);
+        self.reload_pipeline(surface_format, crate::TextRenderPipeline);
+        self.reload_pipeline(surface_format, crate::TextGammaRenderPipeline);
+        self.reload_pipeline(surface_format, crate::Mesh2DRenderPipeline);
+        self.reload_pipeline(surface_format, crate::LightRenderPipeline);
+        self.reload_pipeline(surface_format, crate::RectRenderPipeline);
+        self.rebuild_pipeline_variants(false);
+    }
+
+    /// Recreates one pipeline for [`GpuRenderer::reload_pipelines`] and logs
+    /// the error if it failed validation.
+    ///
+    #[cfg(feature = "shader_hot_reload")]
+    fn reload_pipeline<K: PipeLineLayout>(
+        &mut self,
+        surface_format: wgpu::TextureFormat,
+        pipeline: K,
+    ) {
+        if let Err(e) = self.pipeline_storage.try_create_pipeline(
+            &mut self.device,
+            &mut self.layout_storage,
+            surface_format,
+            pipeline,
+        ) {
+            log::error!(
+                "Pipeline {} failed validation, keeping the previous one: {}",
+                std::any::type_name::<K>(),
+                e
+            );
+        }
+    }
+
     /// Creates each supported rendering objects pipeline.
     ///
     pub fn create_pipelines(&mut self, surface_format: wgpu::TextureFormat) {
@@ -284,6 +644,13 @@ impl GpuRenderer {
             crate::TextRenderPipeline,
         );
 
+        self.pipeline_storage.create_pipeline(
+            &mut self.device,
+            &mut self.layout_storage,
+            surface_format,
+            crate::TextGammaRenderPipeline,
+        );
+
         self.pipeline_storage.create_pipeline(
             &mut self.device,
             &mut self.layout_storage,
@@ -306,6 +673,156 @@ impl GpuRenderer {
         );
     }
 
+    /// Creates a [`crate::ImagePipelineVariant`] that reuses the [`crate::Image`] vertex
+    /// shader, [`crate::ImageVertex`] and bind group layouts but uses a custom fragment shader.
+    /// The fragment shader must take the same VertexOutput as imageshader.wgsl and
+    /// use group 0 for the System Global and group 1 for the texture and sampler.
+    /// Use [`crate::ImageRenderer::set_pipeline_variant`] or
+    /// [`crate::Image::set_pipeline_variant`] to render with it.
+    /// Returns the wgpu validation error and keeps any previous pipeline of
+    /// the variant if the shader does not compile or match the layouts.
+    /// The source is kept so the variant gets rebuilt by
+    /// [`GpuRenderer::set_depth_format`] and `reload_pipelines`.
+    ///
+    /// # Arguments
+    /// - variant: User defined id, Can be a enum cast to u32.
+    /// - fragment_source: WGSL source of the fragment shader module.
+    /// - entry_point: Name of the fragment function within the module.
+    ///
+    pub fn create_image_pipeline_variant(
+        &mut self,
+        variant: u32,
+        fragment_source: &str,
+        entry_point: &str,
+    ) -> Result<(), GraphicsError> {
+        self.build_image_pipeline_variant(
+            variant,
+            fragment_source,
+            entry_point,
+        )?;
+        self.image_variant_sources.insert(
+            variant,
+            (fragment_source.to_owned(), entry_point.to_owned()),
+        );
+        Ok(())
+    }
+
+    /// Creates the Image variant's pipeline within a validation error scope.
+    ///
+    fn build_image_pipeline_variant(
+        &mut self,
+        variant: u32,
+        fragment_source: &str,
+        entry_point: &str,
+    ) -> Result<(), wgpu::Error> {
+        self.device.push_validation_scope();
+        let pipeline = crate::create_image_pipeline(
+            &mut self.device,
+            &mut self.layout_storage,
+            self.window.surface_format,
+            Some(fragment_source),
+            entry_point,
+        );
+        self.device.pop_validation_scope()?;
+
+        self.pipeline_storage
+            .insert_pipeline(crate::ImagePipelineVariant(variant), pipeline);
+        Ok(())
+    }
+
+    /// Creates a [`crate::RectPipelineVariant`] that reuses the [`crate::Rect`] vertex
+    /// shader, [`crate::RectVertex`] and bind group layouts but uses a custom fragment shader.
+    /// The fragment shader must take the same VertexOutput as rectangle_shader.wgsl and
+    /// use group 0 for the System Global and group 1 for the texture and sampler.
+    /// Use [`crate::RectRenderer::set_pipeline_variant`] or
+    /// [`crate::Rect::set_pipeline_variant`] to render with it.
+    /// Returns the wgpu validation error and keeps any previous pipeline of
+    /// the variant if the shader does not compile or match the layouts.
+    /// The source is kept so the variant gets rebuilt by
+    /// [`GpuRenderer::set_depth_format`] and `reload_pipelines`.
+    ///
+    /// # Arguments
+    /// - variant: User defined id, Can be a enum cast to u32.
+    /// - fragment_source: WGSL source of the fragment shader module.
+    /// - entry_point: Name of the fragment function within the module.
+    ///
+    pub fn create_rect_pipeline_variant(
+        &mut self,
+        variant: u32,
+        fragment_source: &str,
+        entry_point: &str,
+    ) -> Result<(), GraphicsError> {
+        self.build_rect_pipeline_variant(
+            variant,
+            fragment_source,
+            entry_point,
+        )?;
+        self.rect_variant_sources.insert(
+            variant,
+            (fragment_source.to_owned(), entry_point.to_owned()),
+        );
+        Ok(())
+    }
+
+    /// Creates the Rect variant's pipeline within a validation error scope.
+    ///
+    fn build_rect_pipeline_variant(
+        &mut self,
+        variant: u32,
+        fragment_source: &str,
+        entry_point: &str,
+    ) -> Result<(), wgpu::Error> {
+        self.device.push_validation_scope();
+        let pipeline = crate::create_rect_pipeline(
+            &mut self.device,
+            &mut self.layout_storage,
+            self.window.surface_format,
+            Some(fragment_source),
+            entry_point,
+        );
+        self.device.pop_validation_scope()?;
+
+        self.pipeline_storage
+            .insert_pipeline(crate::RectPipelineVariant(variant), pipeline);
+        Ok(())
+    }
+
+    /// Rebuilds every Image and Rect pipeline variant from its kept source.
+    /// Failures are logged and if remove_failed is set the variant's
+    /// pipeline is removed so it renders with the default pipeline.
+    ///
+    fn rebuild_pipeline_variants(&mut self, remove_failed: bool) {
+        for (variant, (source, entry)) in self.image_variant_sources.clone() {
+            if let Err(e) =
+                self.build_image_pipeline_variant(variant, &source, &entry)
+            {
+                log::error!(
+                    "Image pipeline variant {variant} failed to rebuild: {e}"
+                );
+
+                if remove_failed {
+                    self.pipeline_storage
+                        .remove_pipeline(crate::ImagePipelineVariant(variant));
+                }
+            }
+        }
+
+        for (variant, (source, entry)) in self.rect_variant_sources.clone() {
+            if let Err(e) =
+                self.build_rect_pipeline_variant(variant, &source, &entry)
+            {
+                log::error!(
+                    "Rect pipeline variant {variant} failed to rebuild: {e}"
+                );
+
+                if remove_failed {
+                    self.pipeline_storage
+                        .remove_pipeline(crate::RectPipelineVariant(variant));
+                }
+            }
+        }
+    }
+
     /// Gets a optional reference of [`wgpu::RenderPipeline`]
     ///
     pub fn get_pipelines<K: PipeLineLayout>(
@@ -0,0 +1,65 @@
+use std::borrow::Cow;
+
+/// Returns the WGSL source of one of the built in shaders.
+/// Without the `shader_hot_reload` feature this is always the embedded source.
+///
+#[cfg(not(feature = "shader_hot_reload"))]
+pub(crate) fn load_shader(
+    _name: &str,
+    embedded: &'static str,
+) -> Cow<'static, str> {
+    Cow::Borrowed(embedded)
+}
+
+/// Returns the WGSL source of one of the built in shaders.
+/// With the `shader_hot_reload` feature the shader is read from the crates
+/// shaders folder, or from `ASCENDING_SHADER_DIR` if set, and validated.
+/// If reading or validation fails the error is logged and the embedded
+/// source is used instead so a bad edit never panics the renderer.
+///
+#[cfg(feature = "shader_hot_reload")]
+pub(crate) fn load_shader(
+    name: &str,
+    embedded: &'static str,
+) -> Cow<'static, str> {
+    let dir = std::env::var("ASCENDING_SHADER_DIR").unwrap_or_else(|_| {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders").to_string()
+    });
+    let path = std::path::Path::new(&dir).join(name);
+
+    let source = match std::fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(e) => {
+            log::error!("Could not read shader {}: {}", path.display(), e);
+            return Cow::Borrowed(embedded);
+        }
+    };
+
+    let module = match naga::front::wgsl::parse_str(&source) {
+        Ok(module) => module,
+        Err(e) => {
+            log::error!(
+                "Shader {} failed to compile:\n{}",
+                name,
+                e.emit_to_string(&source)
+            );
+            return Cow::Borrowed(embedded);
+        }
+    };
+
+    if let Err(e) = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    {
+        log::error!(
+            "Shader {} failed validation:\n{}",
+            name,
+            e.emit_to_string(&source)
+        );
+        return Cow::Borrowed(embedded);
+    }
+
+    Cow::Owned(source)
+}
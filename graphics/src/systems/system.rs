@@ -5,6 +5,39 @@ use glam::{Mat4, Vec2, Vec3, Vec4};
 use input::FrameTime;
 use wgpu::util::DeviceExt;
 
+/// Byte offsets of each field within the shaders struct Global.
+/// These must stay in sync with the Global struct declared in every shader:
+///
+/// ```text
+/// struct Global {
+///     view: mat4x4<f32>,         // 0
+///     proj: mat4x4<f32>,         // 64
+///     inverse_proj: mat4x4<f32>, // 128
+///     eye: vec3<f32>,            // 192
+///     scale: f32,                // 204
+///     size: vec2<f32>,           // 208
+///     seconds: f32,              // 216
+///     manual_view: mat4x4<f32>,  // 224
+///     manual_scale: f32,         // 288
+/// };
+/// ```
+pub mod global_offsets {
+    pub const VIEW: u64 = 0;
+    pub const PROJ: u64 = 64;
+    pub const INVERSE_PROJ: u64 = 128;
+    pub const EYE: u64 = 192;
+    pub const SCALE: u64 = 204;
+    pub const SIZE: u64 = 208;
+    pub const SECONDS: u64 = 216;
+    pub const MANUAL_VIEW: u64 = 224;
+    pub const MANUAL_SCALE: u64 = 288;
+    /// u32 set to 1 when shaders must encode their output to sRGB because
+    /// the Surface format is linear.
+    pub const SRGB_ENCODE: u64 = 292;
+    /// Total size of struct Global including its padding.
+    pub const TOTAL: u64 = 304;
+}
+
 /// System Layout send to all the Shaders for struct Global.
 #[repr(C)]
 #[derive(Clone, Copy, Hash, Pod, Zeroable)]
@@ -40,6 +73,7 @@ pub struct System<Controls: camera::controls::Controls> {
     /// projection, view, eye, inverse view and scale.
     camera: camera::Camera<Controls>,
     /// Screen Size used within the shaders.
+    /// This is in Physical pixels, the same as [`GpuRenderer::size`].
     pub screen_size: [f32; 2],
     /// Buffer to shader struct Global
     global_buffer: wgpu::Buffer,
@@ -82,6 +116,10 @@ where
     }
 
     /// Creates a new [`System`]
+    /// screen_size is expected to be in Physical pixels. Use [`GpuRenderer::size`]
+    /// and not [`GpuRenderer::logical_size`]. All positions given to the shaders
+    /// are in Physical pixels, so Logical UI units must be multiplied by
+    /// [`GpuRenderer::scale_factor`] before being set.
     ///
     pub fn new(
         renderer: &mut GpuRenderer,
@@ -103,7 +141,7 @@ where
         let scale = camera.scale();
         let seconds = 0.0;
 
-        let mut raw = [0f32; 52 + 4 + 20];
+        let mut raw = [0f32; global_offsets::TOTAL as usize / 4];
         raw[..16].copy_from_slice(&AsRef::<[f32; 16]>::as_ref(&view)[..]);
         raw[16..32].copy_from_slice(&AsRef::<[f32; 16]>::as_ref(&proj)[..]);
         raw[32..48]
@@ -115,6 +153,7 @@ where
         raw[56..72]
             .copy_from_slice(&AsRef::<[f32; 16]>::as_ref(&manual_view)[..]);
         raw[72] = manual_scale;
+        raw[73] = f32::from_bits(u32::from(renderer.needs_srgb_encode()));
 
         // Create the uniform buffers.
         let global_buffer = renderer.device().create_buffer_init(
@@ -226,14 +265,14 @@ where
 
             renderer.queue().write_buffer(
                 &self.global_buffer,
-                0,
+                global_offsets::VIEW,
                 bytemuck::cast_slice(&raw),
             );
         }
 
         renderer.queue().write_buffer(
             &self.global_buffer,
-            216,
+            global_offsets::SECONDS,
             bytemuck::bytes_of(&frame_time.seconds()),
         );
 
@@ -246,13 +285,14 @@ where
 
             renderer.queue().write_buffer(
                 &self.global_buffer,
-                224,
+                global_offsets::MANUAL_VIEW,
                 bytemuck::cast_slice(&raw),
             );
         }
     }
 
     /// Updates the GPU's shader struct Global with new screen size information.
+    /// screen_size is expected to be in Physical pixels like [`GpuRenderer::size`].
     ///
     pub fn update_screen(
         &mut self,
@@ -264,7 +304,7 @@ where
 
             renderer.queue().write_buffer(
                 &self.global_buffer,
-                208,
+                global_offsets::SIZE,
                 bytemuck::cast_slice(&screen_size),
             );
         }
@@ -276,6 +316,36 @@ where
         self.camera.view()
     }
 
+    /// Returns the Cameras Scale.
+    ///
+    pub fn scale(&self) -> f32 {
+        self.camera.scale()
+    }
+
+    /// Returns the view Matrix 4x4 the shaders use for a [`CameraType`].
+    ///
+    pub fn view_for(&self, camera_type: CameraType) -> Mat4 {
+        match camera_type {
+            CameraType::None => Mat4::IDENTITY,
+            CameraType::ManualView | CameraType::ManualViewWithScale => {
+                self.manual_view
+            }
+            CameraType::ControlView | CameraType::ControlViewWithScale => {
+                self.camera.view()
+            }
+        }
+    }
+
+    /// Returns the Scale the shaders use for a [`CameraType`].
+    ///
+    pub fn scale_for(&self, camera_type: CameraType) -> f32 {
+        match camera_type {
+            CameraType::ManualViewWithScale => self.manual_scale,
+            CameraType::ControlViewWithScale => self.camera.scale(),
+            _ => 1.0,
+        }
+    }
+
     /// Used to convert bounds information from World into Screen locations with view.
     ///
     pub fn projected_world_to_screen(
@@ -286,15 +356,7 @@ where
         let height = f32::abs(bounds.top - bounds.bottom);
         let projection = self.camera.projection();
         let model = Mat4::IDENTITY;
-        let view = match camera_type {
-            CameraType::None => Mat4::IDENTITY,
-            CameraType::ManualView | CameraType::ManualViewWithScale => {
-                self.manual_view
-            }
-            CameraType::ControlView | CameraType::ControlViewWithScale => {
-                self.camera.view()
-            }
-        };
+        let view = self.view_for(camera_type);
 
         let clip_coords = projection
             * view
@@ -307,19 +369,9 @@ where
             (1.0 - coords.y) * 0.5 * self.screen_size[1],
         );
 
-        let (bw, bh, objh) = match camera_type {
-            CameraType::ManualViewWithScale => (
-                bounds.right * self.manual_scale,
-                bounds.top * self.manual_scale,
-                height * self.manual_scale,
-            ),
-            CameraType::ControlViewWithScale => (
-                bounds.right * self.camera.scale(),
-                bounds.top * self.camera.scale(),
-                height * self.camera.scale(),
-            ),
-            _ => (bounds.right, bounds.top, height),
-        };
+        let scale = self.scale_for(camera_type);
+        let (bw, bh, objh) =
+            (bounds.right * scale, bounds.top * scale, height * scale);
 
         Vec4::new(xy.x, xy.y - objh, bw, bh)
     }
@@ -344,19 +396,9 @@ where
             (1.0 - coords.y) * 0.5 * self.screen_size[1],
         );
 
-        let (bw, bh, objh) = match camera_type {
-            CameraType::ManualViewWithScale => (
-                bounds.right * self.manual_scale,
-                bounds.top * self.manual_scale,
-                height * self.manual_scale,
-            ),
-            CameraType::ControlViewWithScale => (
-                bounds.right * self.camera.scale(),
-                bounds.top * self.camera.scale(),
-                height * self.camera.scale(),
-            ),
-            _ => (bounds.right, bounds.top, height),
-        };
+        let scale = self.scale_for(camera_type);
+        let (bw, bh, objh) =
+            (bounds.right * scale, bounds.top * scale, height * scale);
 
         Vec4::new(xy.x, xy.y - objh, bw, bh)
     }
@@ -0,0 +1,95 @@
+use crate::{AHashMap, BufferLayout, GpuRenderer, Index, InstanceBuffer};
+use std::ops::Range;
+
+/// Pipeline variant ids picked per Object and the instance ranges of each
+/// buffer layer that share a variant once finalized.
+///
+#[derive(Default)]
+pub(crate) struct VariantRuns {
+    /// Variant of each Object added since the last finalize.
+    objects: AHashMap<Index, u32>,
+    /// Instance ranges per buffer layer and the Object variant they use.
+    runs: Vec<Vec<(Range<u32>, Option<u32>)>>,
+}
+
+impl VariantRuns {
+    /// Records the variant an Object renders with until the next finalize.
+    /// None keeps the buffer layer's variant.
+    ///
+    pub(crate) fn insert(&mut self, index: Index, variant: Option<u32>) {
+        if let Some(variant) = variant {
+            self.objects.insert(index, variant);
+        }
+    }
+
+    /// Splits each buffer layer into instance ranges that share a variant.
+    /// Must be called right before [`InstanceBuffer::finalize`] as it sorts
+    /// the unprocessed Objects the same way to know their positions.
+    /// Does nothing when no Object picked a variant.
+    ///
+    pub(crate) fn build<K: BufferLayout>(
+        &mut self,
+        renderer: &GpuRenderer,
+        buffer: &InstanceBuffer<K>,
+    ) {
+        self.runs.clear();
+
+        if self.objects.is_empty() {
+            return;
+        }
+
+        let mut count = 0;
+
+        for processing in &buffer.unprocessed {
+            let mut sorted = processing.clone();
+            let mut layer: Vec<(Range<u32>, Option<u32>)> = Vec::new();
+
+            sorted.sort();
+
+            for index in &sorted {
+                let Some(store) = renderer.get_buffer(index.index) else {
+                    continue;
+                };
+
+                let end = count + (store.store.len() / K::stride()) as u32;
+                let variant = self.objects.get(&index.index).copied();
+
+                match layer.last_mut() {
+                    Some((range, last)) if *last == variant => range.end = end,
+                    _ => layer.push((count..end, variant)),
+                }
+
+                count = end;
+            }
+
+            self.runs.push(layer);
+        }
+
+        self.objects.clear();
+    }
+
+    /// Returns the buffer layer's instance ranges and their variants or None
+    /// when no Object picked a variant.
+    ///
+    pub(crate) fn layer(
+        &self,
+        buffer_layer: usize,
+    ) -> Option<&[(Range<u32>, Option<u32>)]> {
+        self.runs.get(buffer_layer).map(Vec::as_slice)
+    }
+
+    /// Returns the variant of the Object whose instances start at start.
+    ///
+    pub(crate) fn variant_at(
+        &self,
+        buffer_layer: usize,
+        start: u32,
+    ) -> Option<u32> {
+        let runs = self.runs.get(buffer_layer)?;
+        let run = runs.partition_point(|(range, _)| range.end <= start);
+
+        runs.get(run)
+            .filter(|(range, _)| range.contains(&start))
+            .and_then(|(_, variant)| *variant)
+    }
+}
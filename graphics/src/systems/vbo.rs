@@ -1,6 +1,6 @@
 use crate::{
     AsBufferPass, Bounds, Buffer, BufferData, BufferLayout, BufferPass,
-    CameraType, GpuDevice, GpuRenderer, OrderedIndex,
+    CameraType, DrawOrder, GpuDevice, GpuRenderer, Index, OrderedIndex,
 };
 use std::ops::Range;
 
@@ -40,6 +40,9 @@ pub struct VertexBuffer<K: BufferLayout> {
     index_needed: usize,
     /// Deturmines if we need to use Clip the buffer during Rendering.
     is_clipped: bool,
+    /// Sorted (buffer_layer, [`Index`], [`DrawOrder`]) list from the last finalize.
+    /// Only gathered when enabled with set_debug_order.
+    debug_order: Option<Vec<(usize, Index, DrawOrder)>>,
 }
 
 impl<'a, K: BufferLayout> AsBufferPass<'a> for VertexBuffer<K> {
@@ -83,6 +86,7 @@ impl<K: BufferLayout> VertexBuffer<K> {
             index_needed: 0,
             layer_size: layer_size.max(32),
             is_clipped: false,
+            debug_order: None,
         }
     }
 
@@ -100,6 +104,11 @@ impl<K: BufferLayout> VertexBuffer<K> {
         buffer_layer: usize,
     ) {
         if let Some(store) = renderer.get_buffer(index.index) {
+            // Empty stores have nothing to draw.
+            if store.store.is_empty() || store.indexs.is_empty() {
+                return;
+            }
+
             let offset = buffer_layer.saturating_add(1);
             // add in the missing layers this is better than keeping a hash since
             // if at anytime a process adds new data to a older layer it will already Exist.
@@ -127,6 +136,46 @@ impl<K: BufferLayout> VertexBuffer<K> {
         }
     }
 
+    /// Adds many Buffers to the unprocessed list at once. The layers are
+    /// grown once up front which is faster than many
+    /// [`VertexBuffer::add_buffer_store`] calls when adding lots of Objects.
+    ///
+    /// # Arguments
+    /// - indices: The Order Index and Buffer Layer of each Object to render.
+    ///
+    pub fn add_buffer_stores(
+        &mut self,
+        renderer: &GpuRenderer,
+        indices: &[(OrderedIndex, usize)],
+    ) {
+        let Some(max_layer) = indices.iter().map(|(_, layer)| *layer).max()
+        else {
+            return;
+        };
+
+        let offset = max_layer.saturating_add(1);
+
+        if self.unprocessed.len() < offset {
+            self.unprocessed.resize_with(offset, Vec::new);
+        }
+
+        for (index, buffer_layer) in indices {
+            if let Some(store) = renderer.get_buffer(index.index) {
+                // Empty stores have nothing to draw.
+                if store.store.is_empty() || store.indexs.is_empty() {
+                    continue;
+                }
+
+                let mut index = *index;
+
+                self.vertex_needed += store.store.len();
+                self.index_needed += store.indexs.len();
+                index.index_count = store.indexs.len() as u32 / 4;
+                self.unprocessed[*buffer_layer].push(index);
+            }
+        }
+    }
+
     /// Processes all unprocessed listed buffers and uploads any changes to the gpu
     /// This must be called after [`VertexBuffer::add_buffer_store`] in order to Render the Objects.
     pub fn finalize(&mut self, renderer: &mut GpuRenderer) {
@@ -171,11 +220,12 @@ impl<K: BufferLayout> VertexBuffer<K> {
             for buf in processing {
                 let mut write_vertex = false;
                 let mut write_index = false;
+                let mut dirty_range = None;
                 let old_vertex_pos = vertex_pos as u64;
                 let old_index_pos = index_pos as u64;
 
                 if let Some(store) = renderer.get_buffer_mut(buf.index) {
-                    if store.indexs.is_empty() {
+                    if store.indexs.is_empty() || store.store.is_empty() {
                         continue;
                     }
 
@@ -203,6 +253,8 @@ impl<K: BufferLayout> VertexBuffer<K> {
                         store.changed = false;
                     }
 
+                    dirty_range = store.dirty_range.take();
+
                     vertex_pos += store.store.len();
                     index_pos += store.indexs.len();
                 }
@@ -215,6 +267,14 @@ impl<K: BufferLayout> VertexBuffer<K> {
                             old_vertex_pos,
                         );
                     }
+                } else if let Some(dirty) = dirty_range {
+                    if let Some(store) = renderer.get_buffer(buf.index) {
+                        self.vertex_buffer.write(
+                            &renderer.device,
+                            &store.store[dirty.clone()],
+                            old_vertex_pos + dirty.start as u64,
+                        );
+                    }
                 }
 
                 if write_index {
@@ -248,6 +308,16 @@ impl<K: BufferLayout> VertexBuffer<K> {
             }
         }
 
+        if let Some(debug_order) = &mut self.debug_order {
+            debug_order.clear();
+
+            for (layer, processing) in self.unprocessed.iter().enumerate() {
+                debug_order.extend(
+                    processing.iter().map(|buf| (layer, buf.index, buf.order)),
+                );
+            }
+        }
+
         for buffer in &mut self.unprocessed {
             buffer.clear()
         }
@@ -334,6 +404,39 @@ impl<K: BufferLayout> VertexBuffer<K> {
         self.is_clipped
     }
 
+    /// Returns the Buffer Layer's that have something to render after
+    /// [`VertexBuffer::finalize`] from lowest to highest.
+    ///
+    pub fn occupied_layers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| !buffer.is_empty())
+            .map(|(layer, _)| layer)
+    }
+
+    /// Returns one past the highest Buffer Layer with something to render
+    /// after [`VertexBuffer::finalize`] or 0 if there is nothing.
+    ///
+    pub fn layer_count(&self) -> usize {
+        self.occupied_layers().last().map_or(0, |layer| layer + 1)
+    }
+
+    /// Enables or Disables gathering the sorted render order during finalize.
+    /// Used for debugging why an Object renders above or below another.
+    ///
+    pub fn set_debug_order(&mut self, enable: bool) {
+        self.debug_order = if enable { Some(Vec::new()) } else { None };
+    }
+
+    /// Returns the sorted (buffer_layer, [`Index`], [`DrawOrder`]) list from the last
+    /// [`VertexBuffer::finalize`] in the order they were rendered.
+    /// Is empty unless enabled with [`VertexBuffer::set_debug_order`].
+    ///
+    pub fn debug_order(&self) -> &[(usize, Index, DrawOrder)] {
+        self.debug_order.as_deref().unwrap_or(&[])
+    }
+
     /// Sets the Buffer into Clipping mode.
     /// This will Produce a clipped_buffers instead of the buffers which
     /// will still be layered but a Vector of individual objects will Exist rather
@@ -0,0 +1,215 @@
+use crate::{
+    AreaLightLayout, AreaLightRaw, BufferLayout, DirLightLayout,
+    DirectionalLightRaw, GpuRenderer, ImagePipelineVariant,
+    ImageRenderPipeline, ImageVertex, LightRenderPipeline, LightsVertex,
+    MapRenderPipeline, MapVertex, Mesh2DRenderPipeline, Mesh2DVertex,
+    RectPipelineVariant, RectRenderPipeline, RectVertex, StaticVertexBuffer,
+    SystemLayout, TextGammaRenderPipeline, TextRenderPipeline, TextVertex,
+    TextureGroup, TextureLayout, MAX_AREA_LIGHTS, MAX_DIR_LIGHTS,
+};
+use std::{any::TypeId, mem};
+use wgpu::util::{align_to, DeviceExt};
+
+impl GpuRenderer {
+    /// Draws one throwaway instance through every created pipeline into a
+    /// 1x1 Texture. Some drivers only finish compiling a pipeline the first
+    /// time it is drawn with, so calling this during a loading screen moves
+    /// that hitch out of the first rendered frame. This is optional and
+    /// nothing drawn here is ever shown.
+    ///
+    pub fn warm_pipelines(&mut self) {
+        let system_layout = self.create_layout(SystemLayout);
+        let texture_layout = self.create_layout(TextureLayout);
+        let area_layout = self.create_layout(AreaLightLayout);
+        let dir_layout = self.create_layout(DirLightLayout);
+
+        let uniform_group = |layout: &wgpu::BindGroupLayout, size: usize| {
+            let buffer = self.device().create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Warm up uniform buffer"),
+                    contents: &vec![0u8; size],
+                    usage: wgpu::BufferUsages::UNIFORM,
+                },
+            );
+
+            self.device().create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Warm up uniform bind group"),
+                layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            })
+        };
+
+        let system_group = uniform_group(&system_layout, 512);
+        let area_group = uniform_group(
+            &area_layout,
+            MAX_AREA_LIGHTS * align_to(mem::size_of::<AreaLightRaw>(), 32),
+        );
+        let dir_group = uniform_group(
+            &dir_layout,
+            MAX_DIR_LIGHTS
+                * align_to(mem::size_of::<DirectionalLightRaw>(), 48),
+        );
+
+        // Gl can not bind a single layer Texture as a 2D array.
+        let texture = self.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Warm up texture"),
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 2,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let texture_group = TextureGroup::from_view(
+            self,
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(wgpu::TextureViewDimension::D2Array),
+                ..Default::default()
+            }),
+            &texture_layout,
+        );
+
+        let target_size = wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        };
+        let color = self.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Warm up color target"),
+            size: target_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_format(),
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let depth = self.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("Warm up depth target"),
+            size: target_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.depth_format(),
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let color_view = color.create_view(&Default::default());
+        let depth_view = depth.create_view(&Default::default());
+
+        let strides = [
+            StaticVertexBuffer::stride() as usize,
+            ImageVertex::stride(),
+            MapVertex::stride(),
+            TextVertex::stride(),
+            Mesh2DVertex::stride(),
+            LightsVertex::stride(),
+            RectVertex::stride(),
+        ];
+        let max_stride = strides.into_iter().max().unwrap_or(0);
+        // Zeroed vertices make a degenerate triangle so nothing is drawn.
+        let vertices = self.device().create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Warm up vertex buffer"),
+                contents: &vec![0u8; max_stride * 3],
+                usage: wgpu::BufferUsages::VERTEX,
+            },
+        );
+
+        let textured = [&system_group, &texture_group.bind_group];
+        let text = [
+            &system_group,
+            &texture_group.bind_group,
+            &texture_group.bind_group,
+        ];
+        let lights = [&system_group, &area_group, &dir_group];
+        let mesh = [&system_group];
+
+        let mut pipelines: Vec<(&wgpu::RenderPipeline, &[&wgpu::BindGroup])> =
+            Vec::with_capacity(self.pipeline_storage.map.len());
+
+        for (pipeline, groups) in [
+            (self.get_pipelines(ImageRenderPipeline), &textured[..]),
+            (self.get_pipelines(MapRenderPipeline), &textured[..]),
+            (self.get_pipelines(RectRenderPipeline), &textured[..]),
+            (self.get_pipelines(TextRenderPipeline), &text[..]),
+            (self.get_pipelines(TextGammaRenderPipeline), &text[..]),
+            (self.get_pipelines(LightRenderPipeline), &lights[..]),
+            (self.get_pipelines(Mesh2DRenderPipeline), &mesh[..]),
+        ] {
+            if let Some(pipeline) = pipeline {
+                pipelines.push((pipeline, groups));
+            }
+        }
+
+        // Custom fragment variants share the Image and Rect layouts.
+        let variants = [
+            TypeId::of::<ImagePipelineVariant>(),
+            TypeId::of::<RectPipelineVariant>(),
+        ];
+
+        for ((type_id, _), pipeline) in self.pipeline_storage.map.iter() {
+            if variants.contains(type_id) {
+                pipelines.push((pipeline, &textured[..]));
+            }
+        }
+
+        let mut encoder = self.device().create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("Warm up command encoder"),
+            },
+        );
+
+        {
+            let mut pass =
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Warm up render pass"),
+                    color_attachments: &[Some(
+                        wgpu::RenderPassColorAttachment {
+                            view: &color_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                                store: wgpu::StoreOp::Discard,
+                            },
+                        },
+                    )],
+                    depth_stencil_attachment: Some(
+                        wgpu::RenderPassDepthStencilAttachment {
+                            view: &depth_view,
+                            depth_ops: Some(wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(1.0),
+                                store: wgpu::StoreOp::Discard,
+                            }),
+                            stencil_ops: None,
+                        },
+                    ),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+            pass.set_vertex_buffer(0, vertices.slice(..));
+            pass.set_vertex_buffer(1, vertices.slice(..));
+
+            for (pipeline, groups) in pipelines {
+                pass.set_pipeline(pipeline);
+
+                for (i, group) in groups.iter().enumerate() {
+                    pass.set_bind_group(i as u32, *group, &[]);
+                }
+
+                pass.draw(0..3, 0..1);
+            }
+        }
+
+        self.queue().submit(std::iter::once(encoder.finish()));
+    }
+}
@@ -1,7 +1,9 @@
+mod streamer;
 mod texture;
 mod texturegroup;
 mod texturelayout;
 
+pub use streamer::{TextureSource, TextureStreamer};
 pub use texture::Texture;
 pub use texturegroup::TextureGroup;
 pub use texturelayout::TextureLayout;
@@ -0,0 +1,167 @@
+use crate::{AtlasSet, GpuRenderer, Texture, TextureId};
+use log::error;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// Source of a [`Texture`] requested by the [`TextureStreamer`].
+///
+pub enum TextureSource {
+    /// Loads and decodes the image from a File path.
+    Path(PathBuf),
+    /// Decodes the image from encoded bytes in memory.
+    Bytes(Vec<u8>),
+}
+
+/// Decodes [`Texture`]'s on a worker thread and Uploads them to an [`AtlasSet`]
+/// from the render thread within a per frame budget.
+/// This keeps image decoding from stalling the frame.
+///
+pub struct TextureStreamer {
+    /// Sends load requests to the worker thread.
+    sender: Option<Sender<(String, TextureSource)>>,
+    /// Receives decoded [`Texture`]'s from the worker thread.
+    receiver: Receiver<Option<Texture>>,
+    /// Worker thread decoding the textures.
+    worker: Option<JoinHandle<()>>,
+    /// Tells the worker thread to skip the requests still queued.
+    cancelled: Arc<AtomicBool>,
+    /// Amount of requests that have not been polled yet.
+    pending: usize,
+    /// Max amount of [`Texture`]'s uploaded per [`TextureStreamer::poll`].
+    pub upload_budget: usize,
+}
+
+impl TextureStreamer {
+    /// Creates a new [`TextureStreamer`] and starts its worker thread.
+    ///
+    /// # Arguments
+    /// - upload_budget: Max amount of Textures to upload per poll call.
+    ///
+    pub fn new(upload_budget: usize) -> Self {
+        let (sender, requests) = mpsc::channel::<(String, TextureSource)>();
+        let (decoded, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_cancelled = cancelled.clone();
+
+        let worker = thread::spawn(move || {
+            while let Ok((key, source)) = requests.recv() {
+                if worker_cancelled.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let image = match source {
+                    TextureSource::Path(path) => image::open(path),
+                    TextureSource::Bytes(bytes) => {
+                        image::load_from_memory(&bytes)
+                    }
+                };
+
+                let texture = match image {
+                    Ok(image) => Some(Texture::from_image(key, image)),
+                    Err(e) => {
+                        error!("TextureStreamer failed to decode {key}: {e}");
+                        None
+                    }
+                };
+
+                if decoded.send(texture).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            sender: Some(sender),
+            receiver,
+            worker: Some(worker),
+            cancelled,
+            pending: 0,
+            upload_budget: upload_budget.max(1),
+        }
+    }
+
+    /// Requests a [`Texture`] to be decoded on the worker thread.
+    /// The key is used as the [`AtlasSet`] key once uploaded.
+    ///
+    pub fn request(&mut self, key: String, source: TextureSource) {
+        if let Some(sender) = &self.sender {
+            if sender.send((key, source)).is_ok() {
+                self.pending += 1;
+            }
+        }
+    }
+
+    /// Requests a [`Texture`] to be loaded and decoded from a File path.
+    /// The path is used as the [`AtlasSet`] key.
+    ///
+    pub fn request_path(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let key = path.to_string_lossy().into_owned();
+
+        self.request(key, TextureSource::Path(path));
+    }
+
+    /// Requests a [`Texture`] to be decoded from encoded image bytes.
+    ///
+    pub fn request_bytes(&mut self, key: String, bytes: Vec<u8>) {
+        self.request(key, TextureSource::Bytes(bytes));
+    }
+
+    /// Amount of requests that have not been uploaded or failed yet.
+    ///
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Uploads up to upload_budget decoded [`Texture`]'s into the [`AtlasSet`].
+    /// Must be called from the render thread.
+    /// Returns the key and [`AtlasSet`] Index of each uploaded [`Texture`].
+    ///
+    pub fn poll(
+        &mut self,
+        atlas: &mut AtlasSet<String, i32>,
+        renderer: &GpuRenderer,
+    ) -> Vec<(String, TextureId)> {
+        let mut uploaded = Vec::with_capacity(self.upload_budget);
+
+        for _ in 0..self.upload_budget {
+            let Ok(texture) = self.receiver.try_recv() else {
+                break;
+            };
+
+            self.pending = self.pending.saturating_sub(1);
+
+            if let Some(texture) = texture {
+                if let Some(id) = texture.upload(atlas, renderer) {
+                    uploaded.push((texture.name().to_owned(), id));
+                } else {
+                    error!(
+                        "TextureStreamer failed to upload {}",
+                        texture.name()
+                    );
+                }
+            }
+        }
+
+        uploaded
+    }
+}
+
+impl Drop for TextureStreamer {
+    fn drop(&mut self) {
+        // Queued requests are skipped so only the current decode is waited on.
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.sender = None;
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{Allocation, AtlasSet, GpuRenderer, GraphicsError, TileSheet};
+use crate::{
+    Allocation, AtlasSet, GpuRenderer, GraphicsError, TextureId, TileSheet,
+};
 use image::{DynamicImage, GenericImageView, ImageFormat};
 use std::{
     io::{Error, ErrorKind},
@@ -44,7 +46,7 @@ impl Texture {
         path: impl AsRef<Path>,
         atlas: &mut AtlasSet<String, i32>,
         renderer: &GpuRenderer,
-    ) -> Option<usize> {
+    ) -> Option<TextureId> {
         let name = path.as_ref().to_str()?.to_owned();
 
         if let Some(id) = atlas.lookup(&name) {
@@ -63,7 +65,7 @@ impl Texture {
         path: impl AsRef<Path>,
         atlas: &mut AtlasSet<String, i32>,
         renderer: &GpuRenderer,
-    ) -> Option<(usize, Allocation)> {
+    ) -> Option<(TextureId, Allocation)> {
         let name = path.as_ref().to_str()?.to_owned();
 
         if let Some(id) = atlas.lookup(&name) {
@@ -120,7 +122,7 @@ impl Texture {
         &self,
         atlas: &mut AtlasSet<String, i32>,
         renderer: &GpuRenderer,
-    ) -> Option<usize> {
+    ) -> Option<TextureId> {
         let (width, height) = self.size;
         atlas.upload(self.name.clone(), &self.bytes, width, height, 0, renderer)
     }
@@ -132,7 +134,7 @@ impl Texture {
         &self,
         atlas: &mut AtlasSet<String, i32>,
         renderer: &GpuRenderer,
-    ) -> Option<(usize, Allocation)> {
+    ) -> Option<(TextureId, Allocation)> {
         let (width, height) = self.size;
         atlas.upload_with_alloc(
             self.name.clone(),
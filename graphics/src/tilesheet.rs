@@ -1,4 +1,6 @@
-use crate::{AtlasSet, GpuRenderer, Texture};
+use crate::{
+    AtlasSet, GpuRenderer, Texture, TextureId, TileAnimation, Vec2, Vec4,
+};
 use image::{EncodableLayout, ImageBuffer, RgbaImage};
 
 /// Used to map the tile in the tilesheet back visually
@@ -10,7 +12,7 @@ pub struct Tile {
     pub x: u32,
     pub y: u32,
     /// Texture ID to reload the above if needed.
-    pub tex_id: usize,
+    pub tex_id: TextureId,
 }
 
 #[derive(Debug, Default)]
@@ -23,6 +25,75 @@ pub struct TileSheet {
 }
 
 impl TileSheet {
+    /// Creates a [`TileAnimation`] from frame_count tiles starting at
+    /// start_tile with each frame shown for frame_time millsecs.
+    /// Works even if the tiles are scattered within the [`AtlasSet`].
+    ///
+    /// Returns None if the tiles are out of range.
+    ///
+    pub fn as_animation(
+        &self,
+        start_tile: usize,
+        frame_count: usize,
+        frame_time: u32,
+    ) -> Option<TileAnimation> {
+        let tiles = self
+            .tiles
+            .get(start_tile..start_tile.checked_add(frame_count)?)?;
+
+        Some(TileAnimation::new(
+            tiles
+                .iter()
+                .map(|tile| (tile.tex_id.0, frame_time))
+                .collect(),
+        ))
+    }
+
+    /// Returns the texture id, uv, frames and switch_time to set on an
+    /// [`crate::Image`] to animate frame_count tiles starting at start_tile
+    /// with each frame shown for frame_time millsecs.
+    /// The [`crate::Image`] shader steps through frames next to each other in
+    /// the [`AtlasSet`] so this only works if each tile's allocation sits
+    /// right after the last on the same row and layer. Use
+    /// [`TileSheet::as_animation`] when they are scattered.
+    ///
+    /// Returns None if the tiles are out of range or not contiguous.
+    ///
+    pub fn as_image_animation(
+        &self,
+        atlas: &AtlasSet<String, i32>,
+        start_tile: usize,
+        frame_count: usize,
+        frame_time: u32,
+    ) -> Option<(TextureId, Vec4, Vec2, u32)> {
+        let tiles = self
+            .tiles
+            .get(start_tile..start_tile.checked_add(frame_count)?)?;
+        let first = tiles.first()?.tex_id;
+        let (allocation, _) = atlas.peek(first)?;
+        let layer = allocation.layer;
+        let (start_x, start_y, width, height) = allocation.rect();
+
+        for (i, tile) in tiles.iter().enumerate().skip(1) {
+            let (allocation, _) = atlas.peek(tile.tex_id)?;
+            let (x, y, _, _) = allocation.rect();
+
+            if allocation.layer != layer
+                || y != start_y
+                || x != start_x + width * i as u32
+            {
+                return None;
+            }
+        }
+
+        Some((
+            first,
+            Vec4::new(0.0, 0.0, width as f32, height as f32),
+            Vec2::new(frame_count as f32, frame_count as f32),
+            frame_time,
+        ))
+    }
+
     /// Creates a New [`TileSheet`] from a [`Texture`].
     /// This splits the [`Texture`] into [`Tile`]'s using tilesize and places them into the [`AtlasSet`].
     ///
@@ -33,6 +104,21 @@ impl TileSheet {
         renderer: &GpuRenderer,
         atlas: &mut AtlasSet<String, i32>,
         tilesize: u32,
+    ) -> Option<TileSheet> {
+        Self::new_with_threshold(texture, renderer, atlas, tilesize, 0)
+    }
+
+    /// Creates a New [`TileSheet`] like [`TileSheet::new`] but tiles where
+    /// every pixel's alpha is at or below alpha_threshold use the Empty tile.
+    ///
+    /// Returns a Optional [`TileSheet`] upon successful completion.
+    ///
+    pub fn new_with_threshold(
+        texture: Texture,
+        renderer: &GpuRenderer,
+        atlas: &mut AtlasSet<String, i32>,
+        tilesize: u32,
+        alpha_threshold: u8,
     ) -> Option<TileSheet> {
         let tilecount =
             (texture.size().0 / tilesize) * (texture.size().1 / tilesize);
@@ -77,7 +163,7 @@ impl TileSheet {
                 }
             }
 
-            if image.enumerate_pixels().all(|p| p.2 .0[3] == 0) {
+            if image.pixels().all(|p| p.0[3] <= alpha_threshold) {
                 // lets use our only Blank tile. this will always be the first loaded.
                 // We use this when tiles are empty to avoid issues later when we do use
                 // these spots for other tiles.
@@ -121,6 +207,22 @@ impl TileSheet {
         renderer: &GpuRenderer,
         atlas: &mut AtlasSet<String, i32>,
         tilesize: u32,
+    ) -> Option<()> {
+        self.upload_with_threshold(texture, renderer, atlas, tilesize, 0)
+    }
+
+    /// Appends new [`Tile`] like [`TileSheet::upload`] but tiles where
+    /// every pixel's alpha is at or below alpha_threshold use the Empty tile.
+    ///
+    /// Returns a Some(()) upon successful completion.
+    ///
+    pub fn upload_with_threshold(
+        &mut self,
+        texture: Texture,
+        renderer: &GpuRenderer,
+        atlas: &mut AtlasSet<String, i32>,
+        tilesize: u32,
+        alpha_threshold: u8,
     ) -> Option<()> {
         let tilecount =
             (texture.size().0 / tilesize) * (texture.size().1 / tilesize);
@@ -163,7 +265,7 @@ impl TileSheet {
                 }
             }
 
-            if image.enumerate_pixels().all(|p| p.2 .0[3] == 0) {
+            if image.pixels().all(|p| p.0[3] <= alpha_threshold) {
                 self.tiles.push(Tile {
                     x: tilex,
                     y: tiley,
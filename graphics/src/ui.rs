@@ -1,9 +1,13 @@
 mod pipeline;
 mod rectangle;
 mod render;
+mod scroll;
+mod transition;
 mod vertex;
 
 pub use pipeline::*;
 pub use rectangle::*;
 pub use render::*;
+pub use scroll::*;
+pub use transition::*;
 pub use vertex::*;
@@ -1,6 +1,7 @@
 use crate::{
-    AtlasSet, Bounds, CameraType, DrawOrder, GpuRenderer, GraphicsError, Index,
-    OrderedIndex, OtherError, RectVertex, Texture, Vec2, Vec3, Vec4,
+    apply_opacity, AtlasSet, Bounds, CameraType, DrawOrder, GpuRenderer,
+    GraphicsError, Index, OrderedIndex, RectVertex, Texture, TextureId, Vec2,
+    Vec3, Vec4,
 };
 use cosmic_text::Color;
 
@@ -15,7 +16,7 @@ pub struct Rect {
     /// Color of the Rect.
     pub color: Color,
     /// Optional Image Index.
-    pub image: Option<usize>,
+    pub image: Option<TextureId>,
     /// Texture X, Y, W and H if any apply.
     pub uv: Vec4,
     /// Width of the Rects Border.
@@ -36,6 +37,19 @@ pub struct Rect {
     pub bounds: Option<Bounds>,
     /// Overides the absolute order values based on position.
     pub order_override: Option<Vec3>,
+    /// Explicit key to sort by instead of the position.
+    pub sort_key: Option<u64>,
+    /// Anti-aliases the rounded edges and border. Default is true.
+    pub aa: bool,
+    /// Fills the inside of the Rect. When false only the border renders.
+    /// Default is true.
+    pub fill: bool,
+    /// Opacity from 0.0 to 1.0 multiplied into the color and border color
+    /// alpha when rendering. Default is 1.0.
+    pub opacity: f32,
+    /// [`crate::RectPipelineVariant`] id to render with instead of the
+    /// buffer layer's. Used when added with [`crate::RectRenderer::rect_update`].
+    pub pipeline_variant: Option<u32>,
     /// If anything got updated we need to update the buffers too.
     pub changed: bool,
 }
@@ -46,6 +60,30 @@ impl Rect {
     pub fn new(renderer: &mut GpuRenderer, render_layer: u32) -> Self {
         let rect_size = bytemuck::bytes_of(&RectVertex::default()).len();
 
+        Self::with_store(renderer.new_buffer(rect_size, 0), render_layer)
+    }
+
+    /// Creates count new [`Rect`]'s with rendering layer.
+    /// Each is identical to one made with [`Rect::new`] but their
+    /// [`crate::BufferStore`]'s are allocated together.
+    ///
+    pub fn new_many(
+        renderer: &mut GpuRenderer,
+        count: usize,
+        render_layer: u32,
+    ) -> Vec<Self> {
+        let rect_size = bytemuck::bytes_of(&RectVertex::default()).len();
+
+        renderer
+            .new_buffers(count, rect_size, 0)
+            .into_iter()
+            .map(|store_id| Self::with_store(store_id, render_layer))
+            .collect()
+    }
+
+    /// Creates a [`Rect`] with default values using an existing store_id.
+    ///
+    fn with_store(store_id: Index, render_layer: u32) -> Self {
         Self {
             position: Vec3::default(),
             size: Vec2::default(),
@@ -56,11 +94,16 @@ impl Rect {
             border_color: Color::rgba(0, 0, 0, 0),
             radius: 0.0,
             camera_type: CameraType::None,
-            store_id: renderer.new_buffer(rect_size, 0),
+            store_id,
             order: DrawOrder::default(),
             render_layer,
             bounds: None,
             order_override: None,
+            sort_key: None,
+            aa: true,
+            fill: true,
+            opacity: 1.0,
+            pipeline_variant: None,
             changed: true,
         }
     }
@@ -71,6 +114,15 @@ impl Rect {
         renderer.remove_buffer(self.store_id);
     }
 
+    /// Sets an explicit key the [`Rect`] sorts by instead of its position.
+    /// Keyed objects draw after unkeyed ones on the same order layer.
+    ///
+    pub fn set_sort_key(&mut self, sort_key: Option<u64>) -> &mut Self {
+        self.changed = true;
+        self.sort_key = sort_key;
+        self
+    }
+
     /// Updates the [`Rect`]'s order_override.
     ///
     pub fn set_order_override(
@@ -105,6 +157,23 @@ impl Rect {
         self
     }
 
+    /// Sets the [`Rect`]'s opacity. This fades the [`Rect`] without changing
+    /// its colors.
+    ///
+    pub fn set_opacity(&mut self, opacity: f32) -> &mut Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self.changed = true;
+        self
+    }
+
+    /// Sets the [`crate::RectPipelineVariant`] the [`Rect`] renders with
+    /// instead of the buffer layer's. None uses the buffer layer's.
+    ///
+    pub fn set_pipeline_variant(&mut self, variant: Option<u32>) -> &mut Self {
+        self.pipeline_variant = variant;
+        self
+    }
+
     /// Sets the [`Rect`]'s Border Color.
     ///
     pub fn set_border_color(&mut self, color: Color) -> &mut Self {
@@ -123,7 +192,7 @@ impl Rect {
     ) -> Result<&mut Self, GraphicsError> {
         let (id, allocation) =
             Texture::upload_from_with_alloc(path, atlas, renderer)
-                .ok_or_else(|| OtherError::new("failed to upload image"))?;
+                .ok_or(GraphicsError::TextureUploadFailed)?;
 
         let rect = allocation.rect();
 
@@ -133,6 +202,15 @@ impl Rect {
         Ok(self)
     }
 
+    /// Sets the [`Rect`]'s Texture from an already uploaded [`TextureId`].
+    /// Keeps the current container uv so set it with [`Rect::set_container_uv`].
+    ///
+    pub fn set_texture_id(&mut self, texture: Option<TextureId>) -> &mut Self {
+        self.image = texture;
+        self.changed = true;
+        self
+    }
+
     /// Sets the [`Rect`]'s Texture X,Y, W, H details.
     ///
     pub fn set_container_uv(&mut self, uv: Vec4) -> &mut Self {
@@ -173,6 +251,25 @@ impl Rect {
         self
     }
 
+    /// Enables or disables Anti-aliasing of the [`Rect`]'s rounded edges
+    /// and border. Disable it for crisp pixel art Rects.
+    ///
+    pub fn set_aa(&mut self, aa: bool) -> &mut Self {
+        self.aa = aa;
+        self.changed = true;
+        self
+    }
+
+    /// Enables or disables filling the inside of the [`Rect`].
+    /// When disabled only the rounded border band is rendered.
+    /// Default is true.
+    ///
+    pub fn set_fill_enabled(&mut self, fill: bool) -> &mut Self {
+        self.fill = fill;
+        self.changed = true;
+        self
+    }
+
     /// Updates the [`Rect`]'s Buffers to prepare them for rendering.
     ///
     pub fn create_quad(
@@ -206,9 +303,12 @@ impl Rect {
             radius: self.radius,
             uv,
             layer,
-            color: self.color.0,
-            border_color: self.border_color.0,
+            color: apply_opacity(self.color, self.opacity).0,
+            border_color: apply_opacity(self.border_color, self.opacity).0,
             camera_type: self.camera_type as u32,
+            aa: self.aa as u32,
+            fill: self.fill as u32,
+            alpha_mode: atlas.shader_alpha_mode(),
         };
 
         if let Some(store) = renderer.get_buffer_mut(self.store_id) {
@@ -223,8 +323,12 @@ impl Rect {
             None => self.position,
         };
 
-        self.order =
-            DrawOrder::new(self.radius > 0.0, &order_pos, self.render_layer);
+        self.order = DrawOrder::new(
+            self.radius > 0.0 || self.opacity < 1.0 || !self.fill,
+            &order_pos,
+            self.render_layer,
+        )
+        .with_sort_key(self.sort_key);
     }
 
     /// Used to check and update the vertex array.
@@ -0,0 +1,127 @@
+use crate::{Bounds, Vec2};
+use input::{InputEvent, MouseAxis};
+
+/// Scroll state for a clipped area holding other objects.
+/// Keeps the scroll offset clamped between 0 and the content size minus the
+/// viewport size. Add [`ScrollView::child_offset`] to the childrens positions
+/// and give them [`ScrollView::bounds`] as their clipping bounds.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScrollView {
+    /// Visible area the children get clipped to.
+    pub viewport: Bounds,
+    /// Full width and height of the children.
+    pub content_size: Vec2,
+    /// Current scroll offset. x scrolls right and y scrolls down.
+    pub offset: Vec2,
+    /// How far one mouse wheel step scrolls.
+    pub wheel_speed: f32,
+    /// If the offset changed and children need to be moved.
+    pub changed: bool,
+}
+
+impl ScrollView {
+    /// Creates a new [`ScrollView`].
+    ///
+    /// # Arguments
+    /// - viewport: Visible area the children get clipped to.
+    /// - content_size: Full width and height of the children.
+    ///
+    pub fn new(viewport: Bounds, content_size: Vec2) -> Self {
+        Self {
+            viewport,
+            content_size,
+            offset: Vec2::ZERO,
+            wheel_speed: 20.0,
+            changed: true,
+        }
+    }
+
+    /// Sets how far one mouse wheel step scrolls.
+    ///
+    pub fn with_wheel_speed(mut self, wheel_speed: f32) -> Self {
+        self.wheel_speed = wheel_speed;
+        self
+    }
+
+    /// Returns the width and height of the viewport.
+    ///
+    pub fn viewport_size(&self) -> Vec2 {
+        Vec2::new(
+            self.viewport.right - self.viewport.left,
+            self.viewport.top - self.viewport.bottom,
+        )
+    }
+
+    /// Returns the largest offset that can be scrolled to.
+    ///
+    pub fn max_scroll(&self) -> Vec2 {
+        (self.content_size - self.viewport_size()).max(Vec2::ZERO)
+    }
+
+    /// Sets the visible area and clamps the offset to it.
+    ///
+    pub fn set_viewport(&mut self, viewport: Bounds) -> &mut Self {
+        self.viewport = viewport;
+        self.changed = true;
+        self.set_scroll(self.offset)
+    }
+
+    /// Sets the full size of the children and clamps the offset to it.
+    ///
+    pub fn set_content_size(&mut self, content_size: Vec2) -> &mut Self {
+        self.content_size = content_size;
+        self.set_scroll(self.offset)
+    }
+
+    /// Sets the scroll offset clamped between 0 and [`ScrollView::max_scroll`].
+    ///
+    pub fn set_scroll(&mut self, offset: Vec2) -> &mut Self {
+        let offset = offset.clamp(Vec2::ZERO, self.max_scroll());
+
+        if offset != self.offset {
+            self.offset = offset;
+            self.changed = true;
+        }
+
+        self
+    }
+
+    /// Moves the scroll offset by delta and clamps it.
+    ///
+    pub fn scroll_by(&mut self, delta: Vec2) -> &mut Self {
+        self.set_scroll(self.offset + delta)
+    }
+
+    /// Scrolls using [`InputEvent::MouseWheel`]'s. Other events are ignored.
+    /// Returns true if the offset changed.
+    ///
+    pub fn handle_event(&mut self, event: &InputEvent) -> bool {
+        let InputEvent::MouseWheel { amount, axis } = event else {
+            return false;
+        };
+
+        let before = self.offset;
+        let step = -amount * self.wheel_speed;
+
+        match axis {
+            MouseAxis::Horizontal => self.scroll_by(Vec2::new(step, 0.0)),
+            MouseAxis::Vertical => self.scroll_by(Vec2::new(0.0, step)),
+        };
+
+        before != self.offset
+    }
+
+    /// Returns the offset to add to the childrens positions.
+    /// Scrolling down moves children up since y goes up on screen.
+    ///
+    pub fn child_offset(&self) -> Vec2 {
+        Vec2::new(-self.offset.x, self.offset.y)
+    }
+
+    /// Returns the [`Bounds`] to clip the children with.
+    ///
+    pub fn bounds(&self) -> Bounds {
+        self.viewport
+    }
+}
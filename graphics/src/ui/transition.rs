@@ -0,0 +1,174 @@
+use crate::{AtlasSet, Color, GpuRenderer, OrderedIndex, Rect, Vec2, Vec3};
+use input::FrameTime;
+
+/// How a [`Transition`] covers the screen.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransitionMode {
+    /// Fades the whole screen to the color.
+    Fade,
+    /// Wipes the color across the screen from left to right.
+    Wipe,
+    /// Closes a circle around the center of the screen.
+    Iris,
+}
+
+/// If a [`Transition`] covers or uncovers the screen.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransitionDirection {
+    /// Starts clear and ends with the screen covered.
+    Out,
+    /// Starts covered and ends with the screen clear.
+    In,
+}
+
+/// Full screen scene transition drawn with a [`Rect`] over the frame.
+/// Fade animates the opacity, Wipe the width and Iris a border only
+/// circle whose hole shrinks. Render the [`OrderedIndex`] from
+/// [`Transition::update`] with a [`crate::RectRenderer`] on a buffer layer
+/// above the scene. The effect is a solid color and is not dithered.
+///
+pub struct Transition {
+    /// [`Rect`] drawn over the screen.
+    pub rect: Rect,
+    /// How the screen gets covered.
+    pub mode: TransitionMode,
+    /// If the screen gets covered or uncovered.
+    pub direction: TransitionDirection,
+    /// Color the screen is covered with.
+    pub color: Color,
+    /// Length of the Transition in seconds.
+    pub duration: f32,
+    /// Seconds since the Transition started.
+    pub elapsed: f32,
+}
+
+impl Transition {
+    /// Creates a new [`Transition`].
+    ///
+    /// # Arguments
+    /// - mode: How the screen gets covered.
+    /// - direction: If the screen gets covered or uncovered.
+    /// - color: Color the screen is covered with.
+    /// - duration: Length of the Transition in seconds.
+    /// - render_layer: Rendering Layer of the [`Rect`] used in DrawOrder.
+    ///
+    pub fn new(
+        renderer: &mut GpuRenderer,
+        mode: TransitionMode,
+        direction: TransitionDirection,
+        color: Color,
+        duration: f32,
+        render_layer: u32,
+    ) -> Self {
+        Self {
+            rect: Rect::new(renderer, render_layer),
+            mode,
+            direction,
+            color,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Unloads the [`Transition`]'s [`Rect`] from the buffer store.
+    ///
+    pub fn unload(&self, renderer: &mut GpuRenderer) {
+        self.rect.unload(renderer);
+    }
+
+    /// Restarts the [`Transition`] in a direction.
+    ///
+    pub fn start(&mut self, direction: TransitionDirection) -> &mut Self {
+        self.direction = direction;
+        self.elapsed = 0.0;
+        self
+    }
+
+    /// Returns how far along the [`Transition`] is from 0.0 to 1.0.
+    ///
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Returns how much of the screen is covered from 0.0 to 1.0.
+    ///
+    pub fn coverage(&self) -> f32 {
+        match self.direction {
+            TransitionDirection::Out => self.progress(),
+            TransitionDirection::In => 1.0 - self.progress(),
+        }
+    }
+
+    /// Returns true once the [`Transition`] has run for its duration.
+    ///
+    pub fn is_complete(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Advances the [`Transition`] by the [`FrameTime`]'s delta and updates
+    /// the [`Rect`] to cover the current screen size.
+    /// Returns a [`OrderedIndex`] used in Rendering.
+    ///
+    pub fn update(
+        &mut self,
+        renderer: &mut GpuRenderer,
+        atlas: &mut AtlasSet,
+        frame_time: &FrameTime,
+    ) -> OrderedIndex {
+        self.elapsed = (self.elapsed + frame_time.delta_seconds())
+            .min(self.duration.max(0.0));
+
+        let size = renderer.size();
+        let screen = Vec2::new(size.width, size.height);
+        let coverage = self.coverage();
+
+        match self.mode {
+            TransitionMode::Fade => {
+                self.rect
+                    .set_position(Vec3::ZERO)
+                    .set_size(screen)
+                    .set_color(self.color)
+                    .set_opacity(coverage)
+                    .set_radius(0.0)
+                    .set_border_width(0.0)
+                    .set_fill_enabled(true);
+            }
+            TransitionMode::Wipe => {
+                self.rect
+                    .set_position(Vec3::ZERO)
+                    .set_size(Vec2::new(screen.x * coverage, screen.y))
+                    .set_color(self.color)
+                    .set_opacity(1.0)
+                    .set_radius(0.0)
+                    .set_border_width(0.0)
+                    .set_fill_enabled(true);
+            }
+            TransitionMode::Iris => {
+                // Reach past the corners so only the hole shows through.
+                let radius = (screen.length() * 0.5).ceil() + 1.0;
+                let center = screen * 0.5;
+
+                self.rect
+                    .set_position(Vec3::new(
+                        center.x - radius,
+                        center.y - radius,
+                        0.0,
+                    ))
+                    .set_size(Vec2::splat(radius * 2.0))
+                    .set_radius(radius)
+                    .set_border_width(radius * coverage)
+                    .set_border_color(self.color)
+                    .set_opacity(1.0)
+                    .set_fill_enabled(false);
+            }
+        }
+
+        self.rect.update(renderer, atlas)
+    }
+}
@@ -24,6 +24,13 @@ pub struct RectVertex {
     pub radius: f32,
     /// Camera Type numberical.
     pub camera_type: u32,
+    /// 1 to Anti-alias the rounded edges and border, 0 for crisp edges.
+    pub aa: u32,
+    /// 1 or 2 if the Texture has Premultiplied alpha, 2 being a sRGB
+    /// Texture. See [`crate::AlphaMode`].
+    pub alpha_mode: u32,
+    /// 1 to fill the inside of the Rect, 0 to only render the border.
+    pub fill: u32,
 }
 
 impl Default for RectVertex {
@@ -38,13 +45,16 @@ impl Default for RectVertex {
             layer: 0,
             radius: 1.0,
             camera_type: 0,
+            aa: 1,
+            alpha_mode: 0,
+            fill: 1,
         }
     }
 }
 
 impl BufferLayout for RectVertex {
     fn attributes() -> Vec<wgpu::VertexAttribute> {
-        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32x2, 3 => Float32x4, 4 => Uint32, 5 => Float32, 6 => Uint32, 7 => Uint32, 8 => Float32, 9 => Uint32]
+        wgpu::vertex_attr_array![1 => Float32x3, 2 => Float32x2, 3 => Float32x4, 4 => Uint32, 5 => Float32, 6 => Uint32, 7 => Uint32, 8 => Float32, 9 => Uint32, 10 => Uint32, 11 => Uint32, 12 => Uint32]
             .to_vec()
     }
 
@@ -68,6 +78,6 @@ impl BufferLayout for RectVertex {
     }
 
     fn stride() -> usize {
-        std::mem::size_of::<[f32; 15]>()
+        std::mem::size_of::<[f32; 18]>()
     }
 }
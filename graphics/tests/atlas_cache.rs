@@ -0,0 +1,103 @@
+mod common;
+
+use ascending_graphics::{wgpu, AtlasSet, GpuRenderer, TextureId};
+
+/// Uploads a solid square of `value` sized `size` under `key`.
+fn upload(
+    atlas: &mut AtlasSet,
+    renderer: &GpuRenderer,
+    key: &str,
+    size: u32,
+    value: u8,
+) -> TextureId {
+    let bytes = vec![value; (size * size * 4) as usize];
+
+    atlas
+        .upload(key.to_owned(), &bytes, size, size, 0, renderer)
+        .unwrap()
+}
+
+#[test]
+fn import_cache_restores_exact_layout() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+    let mut atlas = AtlasSet::new(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+    );
+
+    let small = upload(&mut atlas, &renderer, "small", 16, 10);
+    let hole = upload(&mut atlas, &renderer, "hole", 64, 20);
+    let large = upload(&mut atlas, &renderer, "large", 128, 30);
+    atlas.remove(hole);
+
+    let cache = atlas.export_cache(&renderer).unwrap();
+    let mut restored = AtlasSet::new(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+    );
+    // Filled first so a re-pack would give different rects.
+    upload(&mut restored, &renderer, "other", 32, 40);
+    restored.import_cache(&renderer, cache.clone()).unwrap();
+
+    assert_eq!(restored.lookup(&"small".to_owned()), Some(small));
+    assert_eq!(restored.lookup(&"large".to_owned()), Some(large));
+    assert_eq!(restored.lookup(&"hole".to_owned()), None);
+    assert_eq!(restored.lookup(&"other".to_owned()), None);
+
+    for id in [small, large] {
+        let original = atlas.get(id).unwrap();
+        let copy = restored.get(id).unwrap();
+
+        assert_eq!(original.rect(), copy.rect());
+        assert_eq!(original.layer, copy.layer);
+    }
+
+    let again = restored.export_cache(&renderer).unwrap();
+
+    assert_eq!(again.layers.len(), cache.layers.len());
+    for (a, b) in again.layers.iter().zip(&cache.layers) {
+        assert!(a.bytes == b.bytes);
+    }
+
+    // Both allocators hold the same free space so they allocate the same.
+    let reused = upload(&mut restored, &renderer, "reused", 64, 50);
+    let expected = upload(&mut atlas, &renderer, "reused", 64, 50);
+
+    assert_eq!(
+        restored.get(reused).unwrap().rect(),
+        atlas.get(expected).unwrap().rect()
+    );
+}
+
+#[test]
+fn import_cache_rejects_mismatched_size() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+    let mut atlas = AtlasSet::new(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+    );
+    let id = upload(&mut atlas, &renderer, "small", 16, 10);
+    let cache = atlas.export_cache(&renderer).unwrap();
+
+    let mut other = AtlasSet::new(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        512,
+    );
+    let kept = upload(&mut other, &renderer, "kept", 16, 10);
+
+    assert!(other.import_cache(&renderer, cache).is_err());
+    assert_eq!(other.lookup(&"kept".to_owned()), Some(kept));
+    assert_eq!(atlas.lookup(&"small".to_owned()), Some(id));
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use ascending_graphics::{wgpu, AtlasSet, GpuRenderer, GraphicsError};
+
+#[test]
+fn deterministic_atlas_rejects_small_sizes() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+
+    let small = AtlasSet::<String, i32>::new_deterministic(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        128,
+        2,
+        4,
+    );
+    assert!(matches!(
+        small,
+        Err(GraphicsError::AtlasSizeTooSmall {
+            size: 128,
+            min: 256
+        })
+    ));
+
+    let atlas = AtlasSet::<String, i32>::new_deterministic(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+        2,
+        4,
+    );
+    assert!(atlas.is_ok());
+}
+
+/// Uploads a fixed mix of sizes and returns every (layer, rect) in order.
+fn upload_sequence(
+    atlas: &mut AtlasSet<String, i32>,
+    renderer: &GpuRenderer,
+) -> Vec<(usize, (u32, u32, u32, u32))> {
+    let sizes = [48, 200, 16, 130, 64, 256, 90, 32, 180, 8];
+
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &size)| {
+            let bytes = vec![i as u8; (size * size * 4) as usize];
+            let id = atlas
+                .upload(format!("{i}"), &bytes, size, size, 0, renderer)
+                .unwrap();
+            let allocation = atlas.get(id).unwrap();
+
+            (allocation.layer, allocation.rect())
+        })
+        .collect()
+}
+
+#[test]
+fn deterministic_atlases_give_identical_allocations() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+
+    let mut first = AtlasSet::new_deterministic(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+        2,
+        8,
+    )
+    .unwrap();
+    let mut second = AtlasSet::new_deterministic(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+        2,
+        8,
+    )
+    .unwrap();
+
+    let layout = upload_sequence(&mut first, &renderer);
+
+    assert!(layout.iter().any(|(layer, _)| *layer > 0));
+    assert_eq!(layout, upload_sequence(&mut second, &renderer));
+}
+
+#[test]
+fn deterministic_atlas_stays_within_max_layers() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+
+    let mut atlas = AtlasSet::<String, i32>::new_deterministic(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        true,
+        256,
+        2,
+        2,
+    )
+    .unwrap();
+    let full = vec![255; 256 * 256 * 4];
+
+    assert!(atlas
+        .upload("a".to_owned(), &full, 256, 256, 0, &renderer)
+        .is_some());
+    assert!(atlas
+        .upload("b".to_owned(), &full, 256, 256, 0, &renderer)
+        .is_some());
+    assert!(atlas
+        .upload("c".to_owned(), &full, 256, 256, 0, &renderer)
+        .is_none());
+    assert_eq!(atlas.layer_capacity(), 2);
+}
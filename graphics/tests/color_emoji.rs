@@ -0,0 +1,84 @@
+mod common;
+
+use ascending_graphics::{
+    cosmic_text::{Attrs, Family, Metrics, Shaping},
+    wgpu, Bounds, Color, GpuRenderer, RenderText, Text, TextAtlas,
+    TextRenderer, Vec2, Vec3,
+};
+
+/// Subset of Noto Color Emoji with PNG CBDT bitmaps for #, *, 0-9, © and ®.
+/// Noto fonts are under the SIL Open Font License.
+const EMOJI_FONT: &[u8] = include_bytes!("fonts/NotoColorEmoji-Partial.ttf");
+
+/// Renders a single emoji on black and captures the frame.
+fn render_emoji(
+    renderer: &mut GpuRenderer,
+    emoji: &str,
+) -> ascending_graphics::FrameCapture {
+    renderer
+        .font_sys_mut()
+        .db_mut()
+        .load_font_data(EMOJI_FONT.to_vec());
+
+    let system = common::system(renderer);
+    let mut atlas = TextAtlas::new(renderer, 256).unwrap();
+    let mut text_renderer = TextRenderer::new(renderer).unwrap();
+    let mut text = Text::new(
+        renderer,
+        Some(Metrics::new(32.0, 32.0)),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec2::new(32.0, 32.0),
+        1.0,
+        0,
+    );
+
+    text.set_buffer_size(renderer, Some(32.0), Some(32.0))
+        .set_bounds(Bounds::new(0.0, 0.0, 32.0, 32.0))
+        .set_default_color(Color::rgba(255, 255, 255, 255))
+        .set_text(
+            renderer,
+            emoji,
+            Attrs::new().family(Family::Name("Noto Color Emoji")),
+            Shaping::Advanced,
+        );
+
+    text_renderer
+        .text_update(&mut text, &mut atlas, renderer, 0)
+        .unwrap();
+    text_renderer.finalize(renderer);
+
+    let mut encoder = common::begin_frame(renderer);
+    {
+        let mut pass = common::begin_pass(
+            renderer,
+            &mut encoder,
+            &system,
+            wgpu::Color::BLACK,
+        );
+        pass.render_all_text(renderer, &text_renderer, &atlas);
+    }
+    common::finish_frame(renderer, encoder)
+}
+
+#[test]
+fn png_color_emoji_keeps_channel_order() {
+    let Some(mut renderer) = common::headless(32, 32) else {
+        return;
+    };
+
+    // Noto draws the copyright sign as a blue tinted gray, so a red and
+    // blue swap makes red the brighter channel.
+    let capture = render_emoji(&mut renderer, "\u{A9}");
+    let [red, green, blue, _] = common::pixel(&capture, 12, 4);
+
+    assert!(red > 0x40, "no emoji was drawn at the sample pixel");
+    assert!(blue > red, "emoji red {red} and blue {blue} are swapped");
+    assert!(green > red && blue > green);
+
+    let (red, blue) = capture
+        .bytes
+        .chunks_exact(4)
+        .fold((0, 0), |sum, p| (sum.0 + p[0] as u64, sum.1 + p[2] as u64));
+
+    assert!(blue > red, "emoji red {red} and blue {blue} are swapped");
+}
@@ -0,0 +1,141 @@
+#![allow(dead_code)]
+
+use ascending_graphics::{
+    camera::{
+        controls::{FlatControls, FlatSettings},
+        Projection,
+    },
+    wgpu, winit, AdapterOptions, AdapterPowerSettings, FrameCapture,
+    GpuRenderer, Mat4, RenderPassBuilder, System,
+};
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+/// Runs a Future to completion on the current thread.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        std::thread::yield_now();
+    }
+}
+
+/// Set to 1 to make GPU tests fail instead of skip when no adapter exists,
+/// so CI with a software adapter can not pass without running them.
+pub const REQUIRE_GPU_ENV: &str = "ASCENDING_REQUIRE_GPU";
+
+/// Creates a headless GpuRenderer or None when the machine has no adapter
+/// so GPU tests can be skipped. Panics instead when [`REQUIRE_GPU_ENV`] is
+/// set to 1.
+pub fn headless(width: u32, height: u32) -> Option<GpuRenderer> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::all(),
+        ..Default::default()
+    });
+    let renderer = block_on(GpuRenderer::new_headless(
+        &instance,
+        AdapterOptions {
+            allowed_backends: wgpu::Backends::all(),
+            power: AdapterPowerSettings::HighPower,
+            compatible_surface: None,
+        },
+        &wgpu::DeviceDescriptor::default(),
+        winit::dpi::PhysicalSize::new(width, height),
+    ));
+
+    match renderer {
+        Ok(renderer) => Some(renderer),
+        Err(error) => {
+            if std::env::var(REQUIRE_GPU_ENV).is_ok_and(|value| value == "1") {
+                panic!("{REQUIRE_GPU_ENV} is set but no adapter: {error}");
+            }
+
+            eprintln!("Skipping GPU test, no headless adapter: {error}");
+            None
+        }
+    }
+}
+
+/// Creates a System with a pixel space orthographic camera.
+pub fn system(renderer: &mut GpuRenderer) -> System<FlatControls> {
+    let size = renderer.size();
+
+    System::new(
+        renderer,
+        Projection::Orthographic {
+            left: 0.0,
+            right: size.width,
+            bottom: 0.0,
+            top: size.height,
+            near: 1.0,
+            far: -100.0,
+        },
+        FlatControls::new(FlatSettings::default()),
+        [size.width, size.height],
+        Mat4::IDENTITY,
+        1.0,
+    )
+}
+
+/// Acquires the headless frame and returns an encoder to record into.
+pub fn begin_frame(renderer: &mut GpuRenderer) -> wgpu::CommandEncoder {
+    renderer
+        .update(&winit::event::WindowEvent::RedrawRequested)
+        .expect("headless frame");
+
+    renderer
+        .device()
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("test encoder"),
+        })
+}
+
+/// Begins a pass that clears the frame to clear and binds the System.
+pub fn begin_pass<'e>(
+    renderer: &GpuRenderer,
+    encoder: &'e mut wgpu::CommandEncoder,
+    system: &System<FlatControls>,
+    clear: wgpu::Color,
+) -> wgpu::RenderPass<'e> {
+    let mut pass = RenderPassBuilder::new()
+        .with_color_clear(clear)
+        .with_depth_clear(1.0)
+        .begin(renderer, encoder)
+        .expect("render pass");
+
+    pass.set_bind_group(0, system.bind_group(), &[]);
+    pass
+}
+
+/// Submits the encoder and reads the frame back.
+pub fn finish_frame(
+    renderer: &mut GpuRenderer,
+    encoder: wgpu::CommandEncoder,
+) -> FrameCapture {
+    renderer.queue().submit(std::iter::once(encoder.finish()));
+
+    let capture = block_on(renderer.capture_frame_async().expect("readback"))
+        .expect("mapped readback");
+
+    renderer.present().expect("present");
+    capture
+}
+
+/// Returns the RGBA bytes of the pixel at x, y from the top left.
+pub fn pixel(capture: &FrameCapture, x: u32, y: u32) -> [u8; 4] {
+    let i = ((y * capture.width + x) * 4) as usize;
+    [
+        capture.bytes[i],
+        capture.bytes[i + 1],
+        capture.bytes[i + 2],
+        capture.bytes[i + 3],
+    ]
+}
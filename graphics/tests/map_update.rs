@@ -0,0 +1,90 @@
+mod common;
+
+use ascending_graphics::{
+    wgpu, AtlasSet, Color, GpuRenderer, Map, MapLayers, MapRenderer, TileData,
+    Vec2,
+};
+
+/// Builds maps with a different tile pattern and tint each.
+fn maps(renderer: &mut GpuRenderer, tile_id: usize, count: usize) -> Vec<Map> {
+    (0..count)
+        .map(|i| {
+            let mut map = Map::new(renderer, 20);
+
+            map.pos = Vec2::new(i as f32 * 640.0, 0.0);
+
+            for x in 0..32 {
+                for y in 0..32 {
+                    if (x + y + i as u32) % 3 == 0 {
+                        map.set_tile(
+                            (x, y, (i % 9) as u32),
+                            TileData {
+                                id: tile_id,
+                                color: Color::rgba(255, x as u8 * 8, 0, 255),
+                            },
+                        );
+                    }
+                }
+            }
+
+            map.set_layer_tint(
+                MapLayers::Fringe,
+                Color::rgba(255, 255, 255, 255 - i as u8),
+            );
+            map
+        })
+        .collect()
+}
+
+#[test]
+fn update_maps_matches_map_update() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+
+    let mut atlas = AtlasSet::new(
+        &mut renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+    );
+    let tile_id = atlas
+        .upload(
+            "tile".to_string(),
+            &[255; 20 * 20 * 4],
+            20,
+            20,
+            0,
+            &renderer,
+        )
+        .unwrap()
+        .0;
+    let mut map_renderer = MapRenderer::new(&mut renderer, 1).unwrap();
+
+    // Enough maps to be split across threads on most machines.
+    let mut single = maps(&mut renderer, tile_id, 12);
+    let mut threaded = maps(&mut renderer, tile_id, 12);
+
+    for map in single.iter_mut() {
+        map_renderer.map_update(map, &mut renderer, &mut atlas, [0, 1]);
+    }
+
+    map_renderer.update_maps(&mut threaded, &mut renderer, &atlas, [0, 1]);
+
+    let mut bytes = 0;
+
+    for (single, threaded) in single.iter().zip(&threaded) {
+        assert_eq!(single.orders, threaded.orders);
+
+        for (a, b) in single.stores.iter().zip(&threaded.stores) {
+            let a = renderer.get_buffer(*a).unwrap();
+            let b = renderer.get_buffer(*b).unwrap();
+
+            assert_eq!(a.store, b.store);
+            assert_eq!(a.indexs, b.indexs);
+            bytes += a.store.len();
+        }
+    }
+
+    assert!(bytes > 0, "no map vertices were built");
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use ascending_graphics::{
+    wgpu, Color, DrawMode, GpuRenderer, Mesh2D, Mesh2DBuilder, Mesh2DRenderer,
+    RenderMesh2D, Vec4,
+};
+
+/// Renders the meshes on black and returns how many pixels were drawn.
+fn render_meshes(renderer: &mut GpuRenderer, meshes: &mut [Mesh2D]) -> usize {
+    let system = common::system(renderer);
+    let mut mesh_renderer = Mesh2DRenderer::new(renderer).unwrap();
+
+    for mesh in meshes.iter_mut() {
+        // Added directly so the render path also has to skip empty meshes.
+        let index = mesh.update(renderer);
+        mesh_renderer.add_buffer_store(renderer, index, 0);
+    }
+
+    mesh_renderer.finalize(renderer);
+
+    let mut encoder = common::begin_frame(renderer);
+    {
+        let mut pass = common::begin_pass(
+            renderer,
+            &mut encoder,
+            &system,
+            wgpu::Color::BLACK,
+        );
+        pass.render_all_2dmeshs(renderer, &mesh_renderer, &system);
+    }
+    let capture = common::finish_frame(renderer, encoder);
+
+    capture
+        .bytes
+        .chunks_exact(4)
+        .filter(|pixel| pixel[..3] != [0, 0, 0])
+        .count()
+}
+
+#[test]
+fn empty_mesh_renders_nothing() {
+    let Some(mut renderer) = common::headless(64, 64) else {
+        return;
+    };
+
+    let mut builder = Mesh2DBuilder::default();
+    builder.finalize();
+
+    let mut empty = Mesh2D::new(&mut renderer, 0);
+    empty.from_builder(&builder);
+
+    assert!(empty.is_empty());
+    assert_eq!(
+        render_meshes(&mut renderer, std::slice::from_mut(&mut empty)),
+        0
+    );
+
+    builder
+        .rectangle(
+            DrawMode::fill(),
+            Vec4::new(16.0, 16.0, 32.0, 32.0),
+            1.0,
+            Color::rgba(255, 255, 255, 255),
+        )
+        .unwrap();
+    builder.finalize();
+
+    let mut filled = Mesh2D::new(&mut renderer, 0);
+    filled.from_builder(&builder);
+
+    let drawn = render_meshes(&mut renderer, &mut [empty, filled]);
+
+    assert!(
+        drawn > 0,
+        "the filled mesh next to the empty one was not drawn"
+    );
+}
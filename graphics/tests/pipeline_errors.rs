@@ -0,0 +1,175 @@
+mod common;
+
+use ascending_graphics::{
+    wgpu, AsBufferPass, AtlasSet, GpuRenderer, GraphicsError, Image,
+    ImageRenderer, RenderImage, SetBuffers, Vec2, Vec3, Vec4,
+};
+
+/// Valid WGSL whose fragment shader reads a bind group the pipeline layout
+/// does not have.
+const MISMATCHED: &str = "
+@group(1) @binding(0) var<uniform> tint: vec4<f32>;
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(f32(index), 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return tint;
+}
+";
+
+#[test]
+fn validation_scope_captures_pipeline_layout_mismatch() {
+    let Some(renderer) = common::headless(16, 16) else {
+        return;
+    };
+    let gpu_device = renderer.gpu_device();
+    let device = gpu_device.device();
+
+    gpu_device.push_validation_scope();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(MISMATCHED.into()),
+    });
+    let layout =
+        device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+    let _pipeline =
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: Some("vertex"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: Some("fragment"),
+                targets: &[Some(renderer.surface_format().into())],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+    assert!(gpu_device.pop_validation_scope().is_err());
+
+    // Nothing failed in this scope.
+    gpu_device.push_validation_scope();
+    assert!(gpu_device.pop_validation_scope().is_ok());
+}
+
+/// Fragment shader of the Image variant that paints every pixel green.
+const GREEN: &str = "
+@fragment
+fn green() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+}
+";
+
+/// Draws three red Images side by side in one buffer layer with only the
+/// middle one using the green variant and returns each center pixel.
+fn render_row(renderer: &mut GpuRenderer) -> [[u8; 4]; 3] {
+    let system = common::system(renderer);
+    let mut atlas = AtlasSet::new(
+        renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+    );
+    let bytes: Vec<u8> = [255, 0, 0, 255].repeat(16);
+    let texture = atlas
+        .upload("red".to_owned(), &bytes, 4, 4, 0, renderer)
+        .unwrap();
+
+    let mut image_renderer = ImageRenderer::new(renderer).unwrap();
+    let mut images: Vec<Image> = (0..3)
+        .map(|i| {
+            let mut image = Image::new(Some(texture), renderer, 0);
+            image.pos = Vec3::new(i as f32 * 16.0, 0.0, 1.0);
+            image.hw = Vec2::new(16.0, 16.0);
+            image.uv = Vec4::new(0.0, 0.0, 4.0, 4.0);
+            image
+        })
+        .collect();
+    images[1].set_pipeline_variant(Some(7));
+
+    for image in &mut images {
+        image_renderer.image_update(image, renderer, &mut atlas, 0);
+    }
+    image_renderer.finalize(renderer);
+
+    let mut encoder = common::begin_frame(renderer);
+    {
+        let mut pass = common::begin_pass(
+            renderer,
+            &mut encoder,
+            &system,
+            wgpu::Color::BLACK,
+        );
+        pass.set_buffers(renderer.buffer_object.as_buffer_pass());
+        pass.render_all_images(renderer, &image_renderer, &atlas, &system);
+    }
+    let capture = common::finish_frame(renderer, encoder);
+
+    [0, 1, 2].map(|i| common::pixel(&capture, i * 16 + 8, 8))
+}
+
+#[test]
+fn image_variant_renders_per_object() {
+    let Some(mut renderer) = common::headless(48, 16) else {
+        return;
+    };
+
+    assert!(renderer
+        .create_image_pipeline_variant(7, MISMATCHED, "fragment")
+        .is_err());
+    renderer
+        .create_image_pipeline_variant(7, GREEN, "green")
+        .unwrap();
+
+    let [left, middle, right] = render_row(&mut renderer);
+    assert_eq!(left, [255, 0, 0, 255]);
+    assert_eq!(middle, [0, 255, 0, 255]);
+    assert_eq!(right, [255, 0, 0, 255]);
+
+    // The variant must be rebuilt for the new depth format.
+    renderer
+        .set_depth_format(wgpu::TextureFormat::Depth24PlusStencil8)
+        .unwrap();
+
+    let [left, middle, _] = render_row(&mut renderer);
+    assert_eq!(left, [255, 0, 0, 255]);
+    assert_eq!(middle, [0, 255, 0, 255]);
+}
+
+#[test]
+fn depth_format_is_validated() {
+    let Some(mut renderer) = common::headless(16, 16) else {
+        return;
+    };
+
+    assert!(matches!(
+        renderer.set_depth_format(wgpu::TextureFormat::Rgba8Unorm),
+        Err(GraphicsError::InvalidDepthFormat(_))
+    ));
+
+    // The headless renderer requests no features so this must be refused.
+    assert!(matches!(
+        renderer.set_depth_format(wgpu::TextureFormat::Depth32FloatStencil8),
+        Err(GraphicsError::MissingDepthFeatures { .. })
+    ));
+    assert_eq!(renderer.depth_format(), wgpu::TextureFormat::Depth32Float);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use ascending_graphics::{
+    wgpu, AlphaMode, AsBufferPass, AtlasSet, GpuRenderer, Image, ImageRenderer,
+    RenderImage, SetBuffers, Vec2, Vec3, Vec4,
+};
+
+/// Background the sprites are drawn over.
+const BACKGROUND: wgpu::Color = wgpu::Color {
+    r: 0.0,
+    g: 0.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+/// Draws a 4x4 sprite over the background and returns the center pixel.
+/// Every pixel of the sprite is the half transparent edge of a red sprite.
+fn render_edge(
+    renderer: &mut GpuRenderer,
+    alpha_mode: AlphaMode,
+    pixel: [u8; 4],
+) -> [u8; 4] {
+    let system = common::system(renderer);
+    let mut atlas = AtlasSet::new(
+        renderer,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+        256,
+    )
+    .with_alpha_mode(alpha_mode);
+    let bytes: Vec<u8> = pixel.repeat(16);
+    let texture = atlas
+        .upload("edge".to_owned(), &bytes, 4, 4, 0, renderer)
+        .unwrap();
+
+    let mut image_renderer = ImageRenderer::new(renderer).unwrap();
+    let mut image = Image::new(Some(texture), renderer, 0);
+    image.pos = Vec3::new(0.0, 0.0, 1.0);
+    image.hw = Vec2::new(16.0, 16.0);
+    image.uv = Vec4::new(0.0, 0.0, 4.0, 4.0);
+
+    image_renderer.image_update(&mut image, renderer, &mut atlas, 0);
+    image_renderer.finalize(renderer);
+
+    let mut encoder = common::begin_frame(renderer);
+    {
+        let mut pass =
+            common::begin_pass(renderer, &mut encoder, &system, BACKGROUND);
+        pass.set_buffers(renderer.buffer_object.as_buffer_pass());
+        pass.render_all_images(renderer, &image_renderer, &atlas, &system);
+    }
+    let capture = common::finish_frame(renderer, encoder);
+
+    common::pixel(&capture, 8, 8)
+}
+
+#[test]
+fn premultiplied_edges_match_straight_edges() {
+    let Some(mut renderer) = common::headless(16, 16) else {
+        return;
+    };
+
+    let straight =
+        render_edge(&mut renderer, AlphaMode::Straight, [255, 0, 0, 128]);
+    let premultiplied =
+        render_edge(&mut renderer, AlphaMode::Premultiplied, [128, 0, 0, 128]);
+    // Premultiplied pixels read as straight give the dark halo.
+    let halo =
+        render_edge(&mut renderer, AlphaMode::Straight, [128, 0, 0, 128]);
+
+    for channel in 0..3 {
+        assert!(
+            straight[channel].abs_diff(premultiplied[channel]) <= 2,
+            "premultiplied edge {premultiplied:?} should match straight edge {straight:?}"
+        );
+    }
+
+    assert!(
+        halo[0] + 16 < premultiplied[0],
+        "reading premultiplied pixels as straight {halo:?} should be darker than {premultiplied:?}"
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use ascending_graphics::{
+    cosmic_text::{Attrs, Metrics, Shaping},
+    wgpu, Bounds, Color, GpuRenderer, RenderText, Text, TextAtlas,
+    TextRenderer, Vec2, Vec3,
+};
+
+/// Renders white text on black and returns the summed red channel.
+fn render_white_text(renderer: &mut GpuRenderer, gamma_correct: bool) -> u64 {
+    let system = common::system(renderer);
+    let mut atlas = TextAtlas::new(renderer, 256).unwrap();
+    let mut text_renderer = TextRenderer::new(renderer).unwrap();
+    let mut text = Text::new(
+        renderer,
+        Some(Metrics::new(24.0, 24.0)),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec2::new(128.0, 32.0),
+        1.0,
+        0,
+    );
+
+    text.set_buffer_size(renderer, Some(128.0), Some(32.0))
+        .set_bounds(Bounds::new(0.0, 0.0, 128.0, 32.0))
+        .set_default_color(Color::rgba(255, 255, 255, 255))
+        .set_text(renderer, "Hamburg", Attrs::new(), Shaping::Advanced);
+
+    text_renderer.set_gamma_correct(gamma_correct);
+    text_renderer
+        .text_update(&mut text, &mut atlas, renderer, 0)
+        .unwrap();
+    text_renderer.finalize(renderer);
+
+    let mut encoder = common::begin_frame(renderer);
+    {
+        let mut pass = common::begin_pass(
+            renderer,
+            &mut encoder,
+            &system,
+            wgpu::Color::BLACK,
+        );
+        pass.render_all_text(renderer, &text_renderer, &atlas);
+    }
+    let capture = common::finish_frame(renderer, encoder);
+
+    capture.bytes.chunks_exact(4).map(|p| p[0] as u64).sum()
+}
+
+#[test]
+fn gamma_correct_text_is_brighter_on_black() {
+    let Some(mut renderer) = common::headless(128, 32) else {
+        return;
+    };
+
+    let linear = render_white_text(&mut renderer, false);
+    let gamma = render_white_text(&mut renderer, true);
+
+    assert!(linear > 0, "no text was drawn");
+    assert!(
+        gamma > linear,
+        "gamma correct coverage {gamma} should be brighter than {linear}"
+    );
+}
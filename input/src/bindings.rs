@@ -2,7 +2,37 @@ use super::axis::Axis;
 use super::button::Button;
 use ahash::AHashMap;
 use serde::{Deserialize, Serialize};
-use std::hash::Hash;
+use std::{hash::Hash, time::Duration};
+use winit::keyboard::ModifiersState;
+
+/// An ordered sequence of button presses that must be completed within a time window.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chord {
+    /// Buttons in the order they must be pressed.
+    pub buttons: Vec<Button>,
+    /// Max Duration allowed between the first and last button press.
+    pub window: Duration,
+}
+
+/// Modifiers an action needs held or needs released to match.
+/// Left and Right modifier keys are treated the same.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct ActionModifiers {
+    /// Modifiers that must all be held.
+    pub required: ModifiersState,
+    /// Modifiers that must not be held.
+    pub forbidden: ModifiersState,
+}
+
+impl ActionModifiers {
+    ///Returns true if the modifiers state has all the required modifiers
+    ///and none of the forbidden ones.
+    pub fn matches(&self, state: ModifiersState) -> bool {
+        state.contains(self.required) && !state.intersects(self.forbidden)
+    }
+}
 
 /// Represents a collection of bindings mapping inputs to actions and axes for various input
 /// devices.
@@ -16,6 +46,12 @@ where
     pub(super) actions: AHashMap<ActionId, Vec<Vec<Button>>>,
     /// A mapping from the axis ID to an array of axes.
     pub(super) axes: AHashMap<AxisId, Vec<Axis>>,
+    /// A mapping from the action ID to an array of ordered chords.
+    #[serde(default)]
+    pub(super) chords: AHashMap<ActionId, Vec<Chord>>,
+    /// A mapping from the action ID to the modifiers it requires or forbids.
+    #[serde(default)]
+    pub(super) action_modifiers: AHashMap<ActionId, ActionModifiers>,
 }
 
 impl<'de, ActionId, AxisId> Bindings<ActionId, AxisId>
@@ -54,10 +90,65 @@ where
         self.axes.insert(id, bindings);
     }
 
+    /// Adds a [`Chord`] binding to the action that triggers when the buttons
+    /// are pressed in order within the window Duration.
+    pub fn insert_chord<B: IntoIterator<Item = Button>>(
+        &mut self,
+        id: ActionId,
+        buttons: B,
+        window: Duration,
+    ) {
+        let chord = Chord {
+            buttons: buttons.into_iter().collect(),
+            window,
+        };
+
+        if chord.buttons.is_empty() {
+            return;
+        }
+
+        // Add the chord to the bindings for the given action ID.
+        if let Some(chords) = self.chords.get_mut(&id) {
+            chords.push(chord);
+            return;
+        }
+
+        // Create the chord bindings for the action ID.
+        let chords = vec![chord];
+        self.chords.insert(id, chords);
+    }
+
+    /// Sets the modifiers the action's buttons and chords need held and
+    /// need released, like Ctrl required and Shift forbidden for Copy.
+    pub fn set_action_modifiers(
+        &mut self,
+        id: ActionId,
+        required: ModifiersState,
+        forbidden: ModifiersState,
+    ) {
+        self.action_modifiers.insert(
+            id,
+            ActionModifiers {
+                required,
+                forbidden,
+            },
+        );
+    }
+
+    /// Removes the modifier requirements from the action.
+    pub fn remove_action_modifiers(
+        &mut self,
+        id: &ActionId,
+    ) -> Option<ActionModifiers> {
+        self.action_modifiers.remove(id)
+    }
+
     pub fn new() -> Self {
         Self {
             actions: AHashMap::new(),
             axes: AHashMap::new(),
+            chords: AHashMap::new(),
+            action_modifiers: AHashMap::new(),
         }
     }
 }
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+/// Error returned when a human readable Duration could not be parsed.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DurationParseError {
+    /// The string was empty.
+    Empty,
+    /// The number part was missing or could not be parsed.
+    InvalidNumber(String),
+    /// The suffix was missing or is not one of ms, s, m or h.
+    InvalidSuffix(String),
+}
+
+impl std::error::Error for DurationParseError {}
+
+impl std::fmt::Display for DurationParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationParseError::Empty => write!(f, "duration string is empty"),
+            DurationParseError::InvalidNumber(num) => {
+                write!(f, "invalid duration number: {num}")
+            }
+            DurationParseError::InvalidSuffix(suffix) => {
+                write!(
+                    f,
+                    "invalid duration suffix: {suffix}. Expected ms, s, m or h"
+                )
+            }
+        }
+    }
+}
+
+/// Adds Human readable formatting and parsing to [`Duration`].
+///
+pub trait DurationExt: Sized {
+    /// Formats the Duration as hours, minutes and seconds rounded to the nearest second.
+    /// Example: "1h 23m 04s", "5m 09s" or "42s".
+    ///
+    fn format_hms(&self) -> String;
+
+    /// Formats the Duration using its largest unit with at most one decimal.
+    /// Example: "250ms", "1.5s", "2m" or "1.2h".
+    ///
+    fn format_compact(&self) -> String;
+
+    /// Parses a Duration from a number followed by a suffix of ms, s, m or h.
+    /// Example: "90s", "1.5m", "250ms" or "2h".
+    ///
+    fn from_str_human(s: &str) -> Result<Self, DurationParseError>;
+}
+
+impl DurationExt for Duration {
+    fn format_hms(&self) -> String {
+        let total = (self.as_millis() + 500) / 1000;
+        let hours = total / 3600;
+        let minutes = (total % 3600) / 60;
+        let seconds = total % 60;
+
+        if hours > 0 {
+            format!("{hours}h {minutes:02}m {seconds:02}s")
+        } else if minutes > 0 {
+            format!("{minutes}m {seconds:02}s")
+        } else {
+            format!("{seconds}s")
+        }
+    }
+
+    fn format_compact(&self) -> String {
+        let seconds = self.as_secs_f64();
+
+        if seconds < 1.0 {
+            format!("{}ms", self.as_millis())
+        } else if round_decimal(seconds) < 60.0 {
+            format!("{}s", trim_decimal(seconds))
+        } else if round_decimal(seconds / 60.0) < 60.0 {
+            format!("{}m", trim_decimal(seconds / 60.0))
+        } else {
+            format!("{}h", trim_decimal(seconds / 3600.0))
+        }
+    }
+
+    fn from_str_human(s: &str) -> Result<Self, DurationParseError> {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Err(DurationParseError::Empty);
+        }
+
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let (number, suffix) = s.split_at(split);
+        let value: f64 = number.parse().map_err(|_| {
+            DurationParseError::InvalidNumber(number.to_owned())
+        })?;
+
+        let seconds = match suffix.trim() {
+            "ms" => value / 1000.0,
+            "s" => value,
+            "m" => value * 60.0,
+            "h" => value * 3600.0,
+            suffix => {
+                return Err(DurationParseError::InvalidSuffix(
+                    suffix.to_owned(),
+                ))
+            }
+        };
+
+        Duration::try_from_secs_f64(seconds)
+            .map_err(|_| DurationParseError::InvalidNumber(number.to_owned()))
+    }
+}
+
+/// Rounds to one decimal.
+fn round_decimal(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Rounds to one decimal and removes it if it is zero.
+fn trim_decimal(value: f64) -> String {
+    let value = round_decimal(value);
+
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_hms_rounds_to_nearest_second() {
+        assert_eq!(Duration::from_millis(499).format_hms(), "0s");
+        assert_eq!(Duration::from_millis(500).format_hms(), "1s");
+        assert_eq!(Duration::from_millis(59_499).format_hms(), "59s");
+        assert_eq!(Duration::from_millis(59_500).format_hms(), "1m 00s");
+        assert_eq!(Duration::from_secs(309).format_hms(), "5m 09s");
+        assert_eq!(Duration::from_millis(3_599_500).format_hms(), "1h 00m 00s");
+        assert_eq!(Duration::from_secs(5044).format_hms(), "1h 24m 04s");
+    }
+
+    #[test]
+    fn format_compact_rounds_to_one_decimal() {
+        assert_eq!(Duration::from_millis(250).format_compact(), "250ms");
+        assert_eq!(Duration::from_millis(999).format_compact(), "999ms");
+        assert_eq!(Duration::from_millis(1000).format_compact(), "1s");
+        assert_eq!(Duration::from_millis(1500).format_compact(), "1.5s");
+        assert_eq!(Duration::from_millis(1549).format_compact(), "1.5s");
+        assert_eq!(Duration::from_millis(59_940).format_compact(), "59.9s");
+        assert_eq!(Duration::from_millis(59_960).format_compact(), "1m");
+        assert_eq!(Duration::from_secs(90).format_compact(), "1.5m");
+        assert_eq!(Duration::from_secs(3597).format_compact(), "1h");
+        assert_eq!(Duration::from_secs(4320).format_compact(), "1.2h");
+    }
+
+    #[test]
+    fn from_str_human_parses_each_suffix() {
+        assert_eq!(
+            Duration::from_str_human("250ms"),
+            Ok(Duration::from_millis(250))
+        );
+        assert_eq!(
+            Duration::from_str_human("90s"),
+            Ok(Duration::from_secs(90))
+        );
+        assert_eq!(
+            Duration::from_str_human(" 1.5m "),
+            Ok(Duration::from_secs(90))
+        );
+        assert_eq!(
+            Duration::from_str_human("2 h"),
+            Ok(Duration::from_secs(7200))
+        );
+    }
+
+    #[test]
+    fn from_str_human_rejects_empty() {
+        assert_eq!(
+            Duration::from_str_human(""),
+            Err(DurationParseError::Empty)
+        );
+        assert_eq!(
+            Duration::from_str_human("   "),
+            Err(DurationParseError::Empty)
+        );
+    }
+
+    #[test]
+    fn from_str_human_rejects_invalid_numbers() {
+        assert_eq!(
+            Duration::from_str_human("s"),
+            Err(DurationParseError::InvalidNumber(String::new()))
+        );
+        assert_eq!(
+            Duration::from_str_human("1.2.3s"),
+            Err(DurationParseError::InvalidNumber("1.2.3".to_owned()))
+        );
+        assert!(matches!(
+            Duration::from_str_human("-5s"),
+            Err(DurationParseError::InvalidNumber(_))
+        ));
+    }
+
+    #[test]
+    fn from_str_human_rejects_invalid_suffixes() {
+        assert_eq!(
+            Duration::from_str_human("10"),
+            Err(DurationParseError::InvalidSuffix(String::new()))
+        );
+        assert_eq!(
+            Duration::from_str_human("10d"),
+            Err(DurationParseError::InvalidSuffix("d".to_owned()))
+        );
+    }
+}
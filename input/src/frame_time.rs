@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Keeps track of Timing useful for Games.
 /// it keeps track of Delta Seconds and Seconds since
@@ -39,6 +39,25 @@ impl FrameTime {
         self.seconds
     }
 
+    /// Returns the std Instant the FrameTime was started at.
+    pub fn start_instant(&self) -> Instant {
+        self.start_time
+    }
+
+    /// Converts Seconds since Start of program into a std Instant.
+    /// Negative seconds are clamped to the start Instant.
+    pub fn instant_at(&self, seconds: f32) -> Instant {
+        self.start_time + Duration::from_secs_f32(seconds.max(0.0))
+    }
+
+    /// Converts a std Instant into Seconds since Start of program.
+    /// Instants from before the start return 0.0.
+    pub fn seconds_at(&self, instant: Instant) -> f32 {
+        instant
+            .saturating_duration_since(self.start_time)
+            .as_secs_f32()
+    }
+
     /// Updates the Timer to get the current Seconds and Delta Seconds.
     pub fn update(&mut self) {
         let frame_time = Instant::now();
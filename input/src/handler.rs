@@ -5,6 +5,7 @@ use super::{
     Key, Location, ModifiersState, MouseButton, PhysicalPosition,
 };
 use ahash::{AHashMap, AHashSet};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::VecDeque,
     hash::Hash,
@@ -18,7 +19,20 @@ use winit::{
     window::Window,
 };
 
-#[derive(Default, PartialEq, Eq, Copy, Clone)]
+/// Max amount of button presses kept to match chords against.
+const CHORD_HISTORY: usize = 16;
+
+/// Default distance in pysical pixels the mouse must move before a drag starts.
+const DEFAULT_DRAG_THRESHOLD: f64 = 4.0;
+
+/// Tracks a held mouse button that might become a drag.
+#[derive(Copy, Clone)]
+struct DragState {
+    start: (f64, f64),
+    dragging: bool,
+}
+
+#[derive(Default, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum MouseButtonAction {
     #[default]
     None,
@@ -73,7 +87,7 @@ impl MouseButtonAction {
     }
 }
 
-#[derive(Default, PartialEq, Copy, Clone)]
+#[derive(Default, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum InputEvent {
     #[default]
     None,
@@ -92,15 +106,39 @@ pub enum InputEvent {
         x: f64,
         y: f64,
     },
+    /// Returns only the direction of the scroll as -1.0 or 1.0.
     MouseWheel {
         amount: f32,
         axis: MouseAxis,
     },
+    /// Raw scroll amount sent with each MouseWheel. This is what gets
+    /// recorded so replays add the same amount to take_scroll_delta.
+    PreciseMouseWheel {
+        amount: f32,
+        axis: MouseAxis,
+    },
     WindowFocused(bool),
     Modifier {
         modifier: Modifier,
         pressed: bool,
     },
+    /// Mouse moved past the drag threshold while the button was held.
+    DragStart {
+        button: MouseButton,
+        start: (f64, f64),
+    },
+    /// Mouse moved while dragging. delta is the movement since the last update.
+    DragUpdate {
+        button: MouseButton,
+        current: (f64, f64),
+        delta: (f64, f64),
+    },
+    /// Button was released after dragging.
+    DragEnd {
+        button: MouseButton,
+        start: (f64, f64),
+        end: (f64, f64),
+    },
 }
 
 impl InputEvent {
@@ -128,6 +166,10 @@ impl InputEvent {
         Self::MouseWheel { amount, axis }
     }
 
+    pub fn precise_mouse_wheel(amount: f32, axis: MouseAxis) -> Self {
+        Self::PreciseMouseWheel { amount, axis }
+    }
+
     pub fn window_focused(focused: bool) -> Self {
         Self::WindowFocused(focused)
     }
@@ -135,9 +177,58 @@ impl InputEvent {
     pub fn modifier(modifier: Modifier, pressed: bool) -> Self {
         Self::Modifier { modifier, pressed }
     }
+
+    pub fn drag_start(button: MouseButton, start: (f64, f64)) -> Self {
+        Self::DragStart { button, start }
+    }
+
+    pub fn drag_update(
+        button: MouseButton,
+        current: (f64, f64),
+        delta: (f64, f64),
+    ) -> Self {
+        Self::DragUpdate {
+            button,
+            current,
+            delta,
+        }
+    }
+
+    pub fn drag_end(
+        button: MouseButton,
+        start: (f64, f64),
+        end: (f64, f64),
+    ) -> Self {
+        Self::DragEnd { button, start, end }
+    }
+
+    ///Returns true for events that come from the window rather than being
+    ///created by the handler. Only these are recorded and replayed.
+    pub fn is_source(&self) -> bool {
+        matches!(
+            self,
+            Self::MouseButton { .. }
+                | Self::KeyInput { .. }
+                | Self::MousePosition { .. }
+                | Self::PreciseMouseWheel { .. }
+                | Self::WindowFocused(_)
+                | Self::Modifier { .. }
+        )
+    }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+)]
 pub enum Modifier {
     LShift,
     RShift,
@@ -149,6 +240,28 @@ pub enum Modifier {
     RSuper,
 }
 
+/// Copy of the [`InputHandler`]'s held input state made with
+/// [`InputHandler::snapshot`] and restored with [`InputHandler::restore`].
+/// Timers are stored relative to when the snapshot was made so they can be restored.
+#[derive(Clone)]
+pub struct InputSnapshot {
+    pub keys: AHashMap<Key, Location>,
+    pub mouse_buttons: AHashSet<winit::event::MouseButton>,
+    pub mouse_position: Option<(f64, f64)>,
+    pub last_mouse_position: Option<(f64, f64)>,
+    pub mouse_delta: (f64, f64),
+    pub modifiers: AHashSet<Modifier>,
+    pub modifiers_state: ModifiersState,
+    pub mouse_button_action: MouseButtonAction,
+    pub window_focused: bool,
+    ///Pending input events. Only Some if asked for when snapshotting.
+    pub input_events: Option<VecDeque<InputEvent>>,
+    ///Time left until the pending mouse button action is sent.
+    mouse_action_remaining: Duration,
+    button_history: Vec<(Button, Duration)>,
+    drags: AHashMap<MouseButton, DragState>,
+}
+
 /// Handler Contains all the Possible Key presses, Modifier Presses and Mouse locations.
 pub struct InputHandler<ActionId, AxisId>
 where
@@ -181,6 +294,22 @@ where
     pub input_events: VecDeque<InputEvent>,
     ///Duration allowed between clicks.
     click_duration: Duration,
+    ///Duration allowed between clicks per mouse button.
+    ///Falls back to click_duration if not set.
+    button_click_durations: AHashMap<MouseButton, Duration>,
+    ///Recent button presses used to match chords in order.
+    button_history: VecDeque<(Button, Instant)>,
+    ///Chord actions that completed and have not been checked yet.
+    triggered_chords: AHashSet<ActionId>,
+    ///Held mouse buttons that are or might become drags.
+    drags: AHashMap<MouseButton, DragState>,
+    ///Distance in pysical pixels the mouse must move before a drag starts.
+    drag_threshold: f64,
+    ///Recorded source events with milliseconds since recording started.
+    recording: Option<Vec<(u64, InputEvent)>>,
+    recording_start: Instant,
+    ///Raw scroll amounts summed since the last take_scroll_delta.
+    scroll_delta: (f32, f32),
 }
 
 impl<ActionId, AxisId> InputHandler<ActionId, AxisId>
@@ -211,6 +340,10 @@ where
         ActionId: std::borrow::Borrow<A>,
         A: Hash + Eq + ?Sized,
     {
+        if !self.action_modifiers_match(action) {
+            return false;
+        }
+
         self.bindings
             .actions
             .get(action)
@@ -224,6 +357,72 @@ where
             .unwrap_or(false)
     }
 
+    ///Returns true if the held modifiers match the action's
+    ///[`crate::ActionModifiers`] or it has none.
+    fn action_modifiers_match<A>(&self, action: &A) -> bool
+    where
+        ActionId: std::borrow::Borrow<A>,
+        A: Hash + Eq + ?Sized,
+    {
+        self.bindings
+            .action_modifiers
+            .get(action)
+            .is_none_or(|modifiers| modifiers.matches(self.modifiers_state))
+    }
+
+    /// Returns true once after the action's [`crate::Chord`] sequence was completed.
+    /// Checking clears the trigger so it returns false until completed again.
+    /// Triggers stay set across later events until checked and are cleared
+    /// when the window loses focus.
+    pub fn is_chord_triggered<A>(&mut self, action: &A) -> bool
+    where
+        ActionId: std::borrow::Borrow<A>,
+        A: Hash + Eq + ?Sized,
+    {
+        self.triggered_chords.remove(action)
+    }
+
+    ///Records a button press and checks if any chords got completed.
+    fn push_chord_button(&mut self, button: Button, timer: Instant) {
+        if self.bindings.chords.is_empty() {
+            return;
+        }
+
+        if self.button_history.len() == CHORD_HISTORY {
+            self.button_history.pop_front();
+        }
+
+        self.button_history.push_back((button, timer));
+
+        for (action, chords) in self.bindings.chords.iter() {
+            let triggered = chords.iter().any(|chord| {
+                let len = chord.buttons.len();
+
+                if len > self.button_history.len() {
+                    return false;
+                }
+
+                let start = self.button_history.len() - len;
+                let pressed = self.button_history.range(start..);
+
+                pressed
+                    .clone()
+                    .map(|(button, _)| button)
+                    .eq(chord.buttons.iter())
+                    && self.button_history[start].1 + chord.window >= timer
+            });
+
+            let modifiers_match =
+                self.bindings.action_modifiers.get(action).is_none_or(
+                    |modifiers| modifiers.matches(self.modifiers_state),
+                );
+
+            if triggered && modifiers_match {
+                self.triggered_chords.insert(action.clone());
+            }
+        }
+    }
+
     ///Checks if a mouse button or key button is down.
     pub fn is_button_down(
         &self,
@@ -344,6 +543,14 @@ where
         self.input_events.pop_front()
     }
 
+    ///Returns the raw (horizontal, vertical) scroll amount summed since the
+    ///last call and resets it. Unlike the MouseWheel events this keeps the
+    ///full precision of trackpads and free spinning wheels.
+    ///Units are lines for wheels and pysical pixels for trackpads.
+    pub fn take_scroll_delta(&mut self) -> (f32, f32) {
+        std::mem::take(&mut self.scroll_delta)
+    }
+
     ///Get Physical mouse position.
     /// This value is a f64 and is not calculated against the DPI.
     pub fn physical_mouse_position(&self) -> Option<(f64, f64)> {
@@ -372,6 +579,52 @@ where
             window_focused: true,
             input_events: VecDeque::with_capacity(12),
             click_duration,
+            button_click_durations: AHashMap::new(),
+            button_history: VecDeque::with_capacity(CHORD_HISTORY),
+            triggered_chords: AHashSet::new(),
+            drags: AHashMap::new(),
+            drag_threshold: DEFAULT_DRAG_THRESHOLD,
+            recording: None,
+            recording_start: Instant::now(),
+            scroll_delta: (0.0, 0.0),
+        }
+    }
+
+    ///Sets the distance in pysical pixels the mouse must move while a
+    ///button is held before a DragStart is sent. Default is 4.0.
+    pub fn set_drag_threshold(&mut self, threshold: f64) {
+        self.drag_threshold = threshold.max(0.0);
+    }
+
+    pub fn get_drag_threshold(&self) -> f64 {
+        self.drag_threshold
+    }
+
+    ///Returns if the mouse button is currently being dragged.
+    pub fn is_dragging(&self, button: MouseButton) -> bool {
+        self.drags.get(&button).is_some_and(|drag| drag.dragging)
+    }
+
+    fn update_drags(&mut self, current: (f64, f64)) {
+        let last = self.last_mouse_position.unwrap_or(current);
+        let delta = (current.0 - last.0, current.1 - last.1);
+
+        for (button, drag) in self.drags.iter_mut() {
+            if !drag.dragging {
+                let (dx, dy) =
+                    (current.0 - drag.start.0, current.1 - drag.start.1);
+
+                if (dx * dx + dy * dy).sqrt() < self.drag_threshold {
+                    continue;
+                }
+
+                drag.dragging = true;
+                self.input_events
+                    .push_back(InputEvent::drag_start(*button, drag.start));
+            }
+
+            self.input_events
+                .push_back(InputEvent::drag_update(*button, current, delta));
         }
     }
 
@@ -383,13 +636,139 @@ where
         self.click_duration
     }
 
-    ///Update the Input Handler based upon the windows events.
-    pub fn window_updates(&mut self, window: &Window, event: &WindowEvent) {
+    ///Sets the duration allowed between clicks for a single mouse button.
+    ///A Duration of zero will disable multi clicks for the button.
+    pub fn set_click_duration_for(
+        &mut self,
+        button: MouseButton,
+        click_duration: Duration,
+    ) {
+        self.button_click_durations.insert(button, click_duration);
+    }
+
+    ///Removes the mouse buttons click duration so it uses the global one.
+    pub fn clear_click_duration_for(&mut self, button: MouseButton) {
+        self.button_click_durations.remove(&button);
+    }
+
+    ///Gets the duration allowed between clicks for a mouse button.
+    ///Returns the global click duration if the button has none set.
+    pub fn get_click_duration_for(&self, button: MouseButton) -> Duration {
+        self.button_click_durations
+            .get(&button)
+            .copied()
+            .unwrap_or(self.click_duration)
+    }
+
+    ///Copies the held keys, buttons, modifiers, positions and click and
+    ///drag timers. Chords that triggered but were not checked are not kept.
+    ///The pending input_events are only kept if include_events is true.
+    pub fn snapshot(&self, include_events: bool) -> InputSnapshot {
+        let now = Instant::now();
+
+        InputSnapshot {
+            keys: self.keys.clone(),
+            mouse_buttons: self.mouse_buttons.clone(),
+            mouse_position: self.mouse_position,
+            last_mouse_position: self.last_mouse_position,
+            mouse_delta: self.mouse_delta,
+            modifiers: self.modifiers.clone(),
+            modifiers_state: self.modifiers_state,
+            mouse_button_action: self.mouse_button_action,
+            window_focused: self.window_focused,
+            input_events: include_events.then(|| self.input_events.clone()),
+            mouse_action_remaining: self
+                .mouse_action_timer
+                .saturating_duration_since(now),
+            button_history: self
+                .button_history
+                .iter()
+                .map(|(button, time)| {
+                    (*button, now.saturating_duration_since(*time))
+                })
+                .collect(),
+            drags: self.drags.clone(),
+        }
+    }
+
+    ///Restores the state saved with [`InputHandler::snapshot`].
+    ///The pending input_events are only replaced if the snapshot kept them.
+    pub fn restore(&mut self, snapshot: InputSnapshot) {
+        let now = Instant::now();
+        let started = |age: Duration| now.checked_sub(age).unwrap_or(now);
+
+        self.keys = snapshot.keys;
+        self.mouse_buttons = snapshot.mouse_buttons;
+        self.mouse_position = snapshot.mouse_position;
+        self.last_mouse_position = snapshot.last_mouse_position;
+        self.mouse_delta = snapshot.mouse_delta;
+        self.modifiers = snapshot.modifiers;
+        self.modifiers_state = snapshot.modifiers_state;
+        self.mouse_button_action = snapshot.mouse_button_action;
+        self.window_focused = snapshot.window_focused;
+        self.mouse_action_timer = now + snapshot.mouse_action_remaining;
+        self.button_history = snapshot
+            .button_history
+            .into_iter()
+            .map(|(button, age)| (button, started(age)))
+            .collect();
+        self.drags = snapshot.drags;
+        self.triggered_chords.clear();
+
+        if let Some(events) = snapshot.input_events {
+            self.input_events = events;
+        }
+    }
+
+    ///Starts recording the input events that change the handlers state
+    ///along with the milliseconds since recording started.
+    ///Clears any previous recording.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+        self.recording_start = Instant::now();
+    }
+
+    ///Returns the current recording. Starts recording if not already.
+    pub fn record(&mut self) -> &Vec<(u64, InputEvent)> {
+        if self.recording.is_none() {
+            self.start_recording();
+        }
+
+        self.recording.get_or_insert_with(Vec::new)
+    }
+
+    ///Stops recording and returns the recorded events.
+    pub fn stop_recording(&mut self) -> Vec<(u64, InputEvent)> {
+        self.recording.take().unwrap_or_default()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    ///Adds the source events pushed since start to the recording.
+    fn record_events(&mut self, start: usize, timer: Instant) {
+        if let Some(recording) = &mut self.recording {
+            let offset = timer
+                .saturating_duration_since(self.recording_start)
+                .as_millis() as u64;
+
+            recording.extend(
+                self.input_events
+                    .range(start..)
+                    .filter(|event| event.is_source())
+                    .map(|event| (offset, *event)),
+            );
+        }
+    }
+
+    ///Sends the pending mouse button action if its click duration ran out.
+    ///Returns the button of the sent action.
+    fn begin_update(&mut self, timer: Instant) -> Option<MouseButton> {
         let mut button_action = None;
 
         //We clear and reset everything here.
         self.last_mouse_position = self.mouse_position;
-        let timer = Instant::now();
 
         if self.mouse_action_timer <= timer
             && self.mouse_button_action.is_some()
@@ -401,6 +780,156 @@ where
             self.mouse_button_action.clear();
         }
 
+        button_action
+    }
+
+    fn key_input(
+        &mut self,
+        key: Key,
+        location: Location,
+        pressed: bool,
+        repeat: bool,
+        timer: Instant,
+    ) {
+        if pressed {
+            self.input_events
+                .push_back(InputEvent::key_input(key, location, true));
+            self.keys.insert(key, location);
+
+            if !repeat {
+                self.push_chord_button(Button::Key(key), timer);
+            }
+        } else if self.keys.remove(&key).is_some() {
+            self.input_events
+                .push_back(InputEvent::key_input(key, location, false));
+        }
+    }
+
+    fn mouse_input(
+        &mut self,
+        button: MouseButton,
+        pressed: bool,
+        timer: Instant,
+        button_action: Option<MouseButton>,
+    ) {
+        if pressed {
+            self.mouse_buttons.insert(button);
+            self.push_chord_button(Button::Mouse(button), timer);
+
+            if let Some(start) = self.mouse_position {
+                self.drags.insert(
+                    button,
+                    DragState {
+                        start,
+                        dragging: false,
+                    },
+                );
+            }
+
+            self.input_events
+                .push_back(InputEvent::mouse_button(button, true));
+
+            if button_action != Some(button) {
+                if !self.mouse_button_action.contains(button) {
+                    if self.mouse_button_action.is_some() {
+                        self.input_events.push_back(
+                            InputEvent::mouse_button_action(
+                                self.mouse_button_action,
+                            ),
+                        );
+                    }
+
+                    self.mouse_button_action.set_single(button);
+                } else {
+                    match self.mouse_button_action {
+                        MouseButtonAction::None
+                        | MouseButtonAction::Triple(_)
+                        | MouseButtonAction::Single(_) => {
+                            self.mouse_button_action.next(button)
+                        }
+                        MouseButtonAction::Double(_) => {
+                            self.mouse_button_action.next(button);
+                            self.input_events.push_back(
+                                InputEvent::mouse_button_action(
+                                    self.mouse_button_action,
+                                ),
+                            );
+                            self.mouse_button_action.clear();
+                        }
+                    }
+                }
+
+                self.mouse_action_timer =
+                    timer + self.get_click_duration_for(button);
+            }
+        } else if self.mouse_buttons.remove(&button) {
+            self.input_events
+                .push_back(InputEvent::mouse_button(button, false));
+
+            if let Some(drag) = self.drags.remove(&button) {
+                if drag.dragging {
+                    self.input_events.push_back(InputEvent::drag_end(
+                        button,
+                        drag.start,
+                        self.mouse_position.unwrap_or(drag.start),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn cursor_moved(&mut self, x: f64, y: f64) {
+        self.input_events
+            .push_back(InputEvent::mouse_position(x, y));
+        self.mouse_position = Some((x, y));
+        self.update_drags((x, y));
+    }
+
+    fn focus_changed(&mut self, focused: bool) {
+        if !focused {
+            self.keys.clear();
+            self.mouse_buttons.clear();
+            self.drags.clear();
+            self.button_history.clear();
+            self.triggered_chords.clear();
+        }
+
+        self.input_events
+            .push_back(InputEvent::window_focused(focused));
+        self.window_focused = focused;
+    }
+
+    fn modifier_input(&mut self, modifier: Modifier, pressed: bool) {
+        if pressed {
+            self.input_events
+                .push_back(InputEvent::modifier(modifier, true));
+            self.modifiers.insert(modifier);
+        } else if self.modifiers.remove(&modifier) {
+            self.input_events
+                .push_back(InputEvent::modifier(modifier, false));
+        }
+    }
+
+    fn mouse_wheel(&mut self, amount: f32, axis: MouseAxis) {
+        match axis {
+            MouseAxis::Horizontal => self.scroll_delta.0 += amount,
+            MouseAxis::Vertical => self.scroll_delta.1 += amount,
+        }
+
+        if amount != 0.0 {
+            self.input_events
+                .push_back(InputEvent::mouse_wheel(amount.signum(), axis));
+            self.input_events
+                .push_back(InputEvent::precise_mouse_wheel(amount, axis));
+        }
+    }
+
+    ///Update the Input Handler based upon the windows events.
+    pub fn window_updates(&mut self, window: &Window, event: &WindowEvent) {
+        let timer = Instant::now();
+        let button_action = self.begin_update(timer);
+        let start = self.input_events.len();
+
         //we enforce it to loop more often to allow for better latency on input returns.
         if self.mouse_button_action != MouseButtonAction::None {
             window.request_redraw();
@@ -414,6 +943,7 @@ where
                         logical_key,
                         location,
                         text,
+                        repeat,
                         ..
                     },
                 ..
@@ -460,75 +990,30 @@ where
                     _ => return,
                 };
 
-                if *state == ElementState::Pressed {
-                    self.input_events
-                        .push_back(InputEvent::key_input(key, *location, true));
-                    self.keys.insert(key, *location);
-                } else if self.keys.remove(&key).is_some() {
-                    self.input_events.push_back(InputEvent::key_input(
-                        key, *location, false,
-                    ));
-                }
+                self.key_input(
+                    key,
+                    *location,
+                    *state == ElementState::Pressed,
+                    *repeat,
+                    timer,
+                );
             }
             WindowEvent::MouseInput { state, button, .. } => {
-                if *state == ElementState::Pressed {
-                    self.mouse_buttons.insert(*button);
-                    self.input_events
-                        .push_back(InputEvent::mouse_button(*button, true));
-
-                    if button_action != Some(*button) {
-                        if !self.mouse_button_action.contains(*button) {
-                            if self.mouse_button_action.is_some() {
-                                self.input_events.push_back(
-                                    InputEvent::mouse_button_action(
-                                        self.mouse_button_action,
-                                    ),
-                                );
-                            }
-
-                            self.mouse_button_action.set_single(*button);
-                        } else {
-                            match self.mouse_button_action {
-                                MouseButtonAction::None
-                                | MouseButtonAction::Triple(_)
-                                | MouseButtonAction::Single(_) => {
-                                    self.mouse_button_action.next(*button)
-                                }
-                                MouseButtonAction::Double(_) => {
-                                    self.mouse_button_action.next(*button);
-                                    self.input_events.push_back(
-                                        InputEvent::mouse_button_action(
-                                            self.mouse_button_action,
-                                        ),
-                                    );
-                                    self.mouse_button_action.clear();
-                                }
-                            }
-                        }
-
-                        self.mouse_action_timer = timer + self.click_duration;
-                    }
-                } else if self.mouse_buttons.remove(button) {
-                    self.input_events
-                        .push_back(InputEvent::mouse_button(*button, false));
-                }
+                self.mouse_input(
+                    *button,
+                    *state == ElementState::Pressed,
+                    timer,
+                    button_action,
+                );
             }
             WindowEvent::CursorMoved {
                 position: PhysicalPosition { x, y },
                 ..
             } => {
-                self.input_events
-                    .push_back(InputEvent::mouse_position(*x, *y));
-                self.mouse_position = Some((*x, *y));
+                self.cursor_moved(*x, *y);
             }
             WindowEvent::Focused(b) => {
-                if !b {
-                    self.keys.clear();
-                    self.mouse_buttons.clear();
-                }
-
-                self.input_events.push_back(InputEvent::window_focused(*b));
-                self.window_focused = *b;
+                self.focus_changed(*b);
             }
             WindowEvent::ModifiersChanged(new_modifiers) => {
                 self.modifiers_state = new_modifiers.state();
@@ -543,14 +1028,10 @@ where
                     (new_modifiers.lsuper_state(), Modifier::LSuper),
                     (new_modifiers.rsuper_state(), Modifier::RSuper),
                 ] {
-                    if state == ModifiersKeyState::Pressed {
-                        self.input_events
-                            .push_back(InputEvent::modifier(modifier, true));
-                        self.modifiers.insert(modifier);
-                    } else if self.modifiers.remove(&modifier) {
-                        self.input_events
-                            .push_back(InputEvent::modifier(modifier, false));
-                    }
+                    self.modifier_input(
+                        modifier,
+                        state == ModifiersKeyState::Pressed,
+                    );
                 }
             }
             WindowEvent::MouseWheel {
@@ -565,22 +1046,67 @@ where
                     }
                 };
 
-                if x != 0.0 {
-                    self.input_events.push_back(InputEvent::mouse_wheel(
-                        x.signum(),
-                        MouseAxis::Horizontal,
-                    ));
-                }
+                self.mouse_wheel(x, MouseAxis::Horizontal);
+                self.mouse_wheel(y, MouseAxis::Vertical);
+            }
+            _ => (),
+        }
 
-                if y != 0.0 {
-                    self.input_events.push_back(InputEvent::mouse_wheel(
-                        y.signum(),
-                        MouseAxis::Vertical,
-                    ));
-                }
+        self.record_events(start, timer);
+    }
+
+    ///Feeds a recorded [`InputEvent`] through the same code paths as
+    ///window_updates. Events created by the handler such as
+    ///MouseButtonAction and drags are ignored as they get recreated.
+    ///timer is the time the event happened at.
+    pub fn replay_event(&mut self, event: InputEvent, timer: Instant) {
+        let button_action = self.begin_update(timer);
+        let start = self.input_events.len();
+
+        match event {
+            InputEvent::KeyInput {
+                key,
+                location,
+                pressed,
+            } => {
+                let repeat = pressed && self.keys.contains_key(&key);
+                self.key_input(key, location, pressed, repeat, timer);
+            }
+            InputEvent::MouseButton { button, pressed } => {
+                self.mouse_input(button, pressed, timer, button_action);
+            }
+            InputEvent::MousePosition { x, y } => self.cursor_moved(x, y),
+            InputEvent::MouseWheel { amount, axis }
+            | InputEvent::PreciseMouseWheel { amount, axis } => {
+                self.mouse_wheel(amount, axis)
+            }
+            InputEvent::WindowFocused(focused) => self.focus_changed(focused),
+            InputEvent::Modifier { modifier, pressed } => {
+                self.modifier_input(modifier, pressed);
+                self.modifiers_state = self.modifiers_from_set();
             }
             _ => (),
         }
+
+        self.record_events(start, timer);
+    }
+
+    ///Builds the ModifiersState from the modifiers that are held.
+    fn modifiers_from_set(&self) -> ModifiersState {
+        let mut state = ModifiersState::empty();
+
+        for modifier in &self.modifiers {
+            state |= match modifier {
+                Modifier::LShift | Modifier::RShift => ModifiersState::SHIFT,
+                Modifier::LAlt | Modifier::RAlt => ModifiersState::ALT,
+                Modifier::LControl | Modifier::RControl => {
+                    ModifiersState::CONTROL
+                }
+                Modifier::LSuper | Modifier::RSuper => ModifiersState::SUPER,
+            };
+        }
+
+        state
     }
 
     pub fn device_updates(&mut self, window: &Window, event: &DeviceEvent) {
@@ -609,3 +1135,100 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replayed_wheel_keeps_precise_amount() {
+        let mut handler: InputHandler<String, String> =
+            InputHandler::new(Bindings::new(), Duration::from_millis(250));
+        let timer = Instant::now();
+
+        handler.start_recording();
+        handler.replay_event(
+            InputEvent::precise_mouse_wheel(2.5, MouseAxis::Vertical),
+            timer,
+        );
+        handler.replay_event(
+            InputEvent::precise_mouse_wheel(-0.25, MouseAxis::Horizontal),
+            timer,
+        );
+
+        let recording = handler.stop_recording();
+
+        assert_eq!(handler.take_scroll_delta(), (-0.25, 2.5));
+        assert!(recording.iter().all(|(_, event)| matches!(
+            event,
+            InputEvent::PreciseMouseWheel { .. }
+        )));
+
+        let mut replayed: InputHandler<String, String> =
+            InputHandler::new(Bindings::new(), Duration::from_millis(250));
+
+        for (_, event) in recording {
+            replayed.replay_event(event, timer);
+        }
+
+        assert_eq!(replayed.take_scroll_delta(), (-0.25, 2.5));
+        assert!(replayed.events().into_iter().any(|event| matches!(
+            event,
+            InputEvent::MouseWheel { amount, .. } if amount == 1.0
+        )));
+    }
+
+    #[test]
+    fn chord_trigger_survives_later_events() {
+        let mut bindings = Bindings::new();
+        bindings.insert_chord(
+            "open".to_owned(),
+            [
+                Button::Key(Key::Named(NamedKey::Control)),
+                Button::Key(Key::Character('k')),
+            ],
+            Duration::from_secs(1),
+        );
+
+        let mut handler: InputHandler<String, String> =
+            InputHandler::new(bindings, Duration::from_millis(250));
+        let timer = Instant::now();
+        let control = Key::Named(NamedKey::Control);
+        let k = Key::Character('k');
+
+        for event in [
+            InputEvent::key_input(control, Location::Left, true),
+            InputEvent::key_input(k, Location::Standard, true),
+            InputEvent::key_input(k, Location::Standard, false),
+            InputEvent::mouse_position(10.0, 20.0),
+        ] {
+            handler.replay_event(event, timer);
+        }
+
+        assert!(handler.is_chord_triggered("open"));
+        assert!(!handler.is_chord_triggered("open"));
+    }
+
+    #[test]
+    fn restore_keeps_pending_click_deadline() {
+        let click_duration = Duration::from_secs(60);
+        let mut handler: InputHandler<String, String> =
+            InputHandler::new(Bindings::new(), click_duration);
+        let pressed = Instant::now();
+
+        handler.replay_event(
+            InputEvent::mouse_button(MouseButton::Left, true),
+            pressed,
+        );
+
+        let snapshot = handler.snapshot(false);
+        let mut restored: InputHandler<String, String> =
+            InputHandler::new(Bindings::new(), click_duration);
+        restored.restore(snapshot);
+
+        // The click must still be pending rather than already expired.
+        assert!(restored.mouse_action_timer > Instant::now());
+        assert!(restored.mouse_action_timer <= pressed + click_duration * 2);
+        assert!(restored.mouse_button_action.is_some());
+    }
+}
@@ -1,16 +1,22 @@
 mod axis;
 mod bindings;
 mod button;
+mod duration;
 mod frame_time;
 mod handler;
 mod keys;
+mod playback;
 
 pub use axis::{Axis, MouseAxis};
-pub use bindings::Bindings;
+pub use bindings::{ActionModifiers, Bindings, Chord};
 pub use button::Button;
+pub use duration::{DurationExt, DurationParseError};
 pub use frame_time::FrameTime;
-pub use handler::{InputEvent, InputHandler, Modifier, MouseButtonAction};
+pub use handler::{
+    InputEvent, InputHandler, InputSnapshot, Modifier, MouseButtonAction,
+};
 pub use keys::{Key, Location, Named};
+pub use playback::InputPlayback;
 pub use winit::{
     dpi::PhysicalPosition, event::MouseButton, keyboard::ModifiersState,
 };
@@ -0,0 +1,81 @@
+use crate::{InputEvent, InputHandler};
+use serde::{Deserialize, Serialize};
+use std::{
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Plays back events recorded with [`InputHandler::record`].
+/// Events are fed through [`InputHandler::replay_event`] so the handler
+/// state updates the same way it does from window events.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InputPlayback {
+    ///Recorded events with milliseconds since recording started.
+    pub events: Vec<(u64, InputEvent)>,
+    ///Index of the next event to play.
+    #[serde(skip)]
+    position: usize,
+    #[serde(skip, default = "Instant::now")]
+    start: Instant,
+}
+
+impl InputPlayback {
+    pub fn new(events: Vec<(u64, InputEvent)>) -> Self {
+        Self {
+            events,
+            position: 0,
+            start: Instant::now(),
+        }
+    }
+
+    ///Restarts playback from the first event.
+    pub fn restart(&mut self) {
+        self.position = 0;
+        self.start = Instant::now();
+    }
+
+    ///Plays every event recorded up to the time since playback started.
+    ///Returns the amount of events played.
+    pub fn update<ActionId, AxisId>(
+        &mut self,
+        handler: &mut InputHandler<ActionId, AxisId>,
+    ) -> usize
+    where
+        ActionId: Clone + Eq + Hash + Send + Sync,
+        AxisId: Clone + Eq + Hash + Send + Sync,
+    {
+        let elapsed = self.start.elapsed().as_millis() as u64;
+        self.advance_to(handler, elapsed)
+    }
+
+    ///Plays every event recorded at or before offset milliseconds.
+    ///Does not depend on the wall clock so it is deterministic for tests.
+    ///Returns the amount of events played.
+    pub fn advance_to<ActionId, AxisId>(
+        &mut self,
+        handler: &mut InputHandler<ActionId, AxisId>,
+        offset: u64,
+    ) -> usize
+    where
+        ActionId: Clone + Eq + Hash + Send + Sync,
+        AxisId: Clone + Eq + Hash + Send + Sync,
+    {
+        let begin = self.position;
+
+        while let Some(&(at, event)) = self.events.get(self.position) {
+            if at > offset {
+                break;
+            }
+
+            handler.replay_event(event, self.start + Duration::from_millis(at));
+            self.position += 1;
+        }
+
+        self.position - begin
+    }
+
+    ///Returns true when all events have been played.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.events.len()
+    }
+}